@@ -1,3 +1,4 @@
+use clap::builder::PossibleValue;
 use clap::Arg;
 use clap::ArgAction;
 use clap::Command;
@@ -11,6 +12,11 @@ pub mod options {
     pub mod path {
         pub static ABSOLUTE: &str = "absolute-path";
         pub static RELATIVE: &str = "relative-path";
+        pub static HYPERLINK: &str = "hyperlink-entries";
+        pub static QUESTION: &str = "hide-control-chars";
+        pub static LITERAL: &str = "show-control-chars";
+        pub static QUOTING: &str = "quoting-style";
+        pub static CLASSIFY: &str = "classify-entries";
     }
 
     pub mod sort {
@@ -21,31 +27,71 @@ pub mod options {
     }
 
     pub mod color {
-        pub static COLOR: &str = "color-entries"; // TODO: Remove
+        pub static COLOR: &str = "color-entries";
         pub static COLORLESS: &str = "no-color-entries";
+        pub static THEME: &str = "theme-config";
+        pub static ICONS: &str = "show-entry-icons";
     }
 
     pub mod read {
         pub static ALL: &str = "read-all-entries";
         pub static VISIBLE: &str = "read-visible-entries";
         pub static FOLDER: &str = "read-folders";
+        pub static FOLLOW: &str = "follow-symlinks";
+        pub static ONEFS: &str = "one-file-system";
+        pub static MOUNTS: &str = "show-mount-points";
+    }
+
+    pub mod detect {
+        pub static CONTENT: &str = "detect-type-by-content";
+        pub static CATEGORIES: &str = "file-categories-config";
     }
 
     pub mod meta {
         pub static META: &str = "show-all-default-metadata";
+        pub static INODE: &str = "show-entry-inode";
+        pub static DEVICE: &str = "show-entry-device";
+        pub static LINKS: &str = "show-entry-hard-links";
         pub static PERMISSION: &str = "show-entry-permission";
+        pub static OCTAL: &str = "show-entry-octal-permission";
+        pub static ACL: &str = "show-entry-acl-marker";
+        pub static GIT: &str = "show-entry-git-status";
+        pub static GITLOG: &str = "show-entry-last-commit";
+        pub static USER: &str = "show-entry-owner";
+        pub static GROUP: &str = "show-entry-group";
         pub static BTIME: &str = "show-entries-creation-time";
         pub static MTIME: &str = "show-entries-modification-time";
         pub static ATIME: &str = "show-entries-access-time";
+        pub static CTIME: &str = "show-entries-change-time";
         pub static SIZE: &str = "show-entries-size";
+        pub static LINES: &str = "show-entries-line-count";
+        pub static DISKUSAGE: &str = "show-entries-disk-usage";
+        pub static XATTR: &str = "show-entry-extended-attributes";
+        pub static CONTEXT: &str = "show-entry-security-context";
+    }
+
+    pub mod time {
+        pub static FORMAT: &str = "time-format";
+        pub static STYLE: &str = "time-style";
+        pub static UTC: &str = "time-in-utc";
+        pub static NANOS: &str = "time-with-nanoseconds";
+    }
+
+    pub mod digest {
+        pub static HASH: &str = "show-entries-digest";
+        pub static MANIFEST: &str = "write-checksum-manifest";
+        pub static VERIFY: &str = "verify-checksum-manifest";
     }
 
     pub mod report {
         pub static YIELD: &str = "show-exhaustive-report";
+        pub static CODE: &str = "show-line-count-by-language";
     }
 
     pub mod branch {
         pub static NOBRANCH: &str = "discard-branch-stick-from-output";
+        pub static CHARSET: &str = "branch-charset";
+        pub static INDENT: &str = "branch-indent-width";
     }
 }
 
@@ -74,6 +120,42 @@ pub fn tree_app() -> Command {
                 .help("Print file/dir name along with it relative path")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::path::QUESTION)
+                .short('q')
+                .help("Print non-printable characters in names as '?', same as --quoting-style=question.")
+                .overrides_with_all([options::path::LITERAL, options::path::QUOTING])
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::path::LITERAL)
+                .short('N')
+                .help("Print names raw, same as --quoting-style=literal.")
+                .overrides_with_all([options::path::QUESTION, options::path::QUOTING])
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::path::QUOTING)
+                .long("quoting-style")
+                .num_args(1)
+                .value_name("STYLE")
+                .value_parser(["literal", "question", "shell-escape", "c"])
+                .help("Quote names with STYLE. Defaults to 'question' on terminals and 'literal' otherwise.")
+                .overrides_with_all([options::path::QUESTION, options::path::LITERAL])
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new(options::path::HYPERLINK)
+                .long("hyperlink")
+                .help("Make entry names clickable file:// links in terminals supporting OSC 8.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::path::CLASSIFY)
+                .long("classify")
+                .help("Append an indicator (one of /*|=) to entry names and symlink targets.")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::sort::REVERSE)
                 .long("reverse")
@@ -106,16 +188,36 @@ pub fn tree_app() -> Command {
             Arg::new(options::color::COLOR)
                 .long("color")
                 .short('c')
-                .help("Print entries with color.")
-                .action(ArgAction::SetTrue),
+                .num_args(0..=1)
+                .require_equals(true)
+                .value_name("WHEN")
+                .value_parser(["auto", "always", "never"])
+                .default_value("auto")
+                .default_missing_value("always")
+                .help("When to print entries with color. 'auto' colors only terminals, honouring NO_COLOR and CLICOLOR_FORCE.")
+                .action(ArgAction::Set),
         )
         .arg(
             Arg::new(options::color::COLORLESS)
                 .long("color-less")
                 .short('C')
-                .help("Print entries without color.")
+                .help("Print entries without color, same as --color=never.")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::color::ICONS)
+                .long("icons")
+                .help("Print a Nerd Font icon before each entry's name.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::color::THEME)
+                .long("theme")
+                .num_args(1)
+                .help("Read colors and branch glyphs from the given TOML file instead of the user config.")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new(options::read::VISIBLE)
                 .long("visible")
@@ -134,6 +236,40 @@ pub fn tree_app() -> Command {
                 .help("Print directoris only.")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::read::FOLLOW)
+                .long("follow")
+                .short('l')
+                .help("Descend into symlinked directories, skipping ones that loop back.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::read::ONEFS)
+                .long("one-file-system")
+                .short('x')
+                .help("Stay on the root's filesystem, marking mount points instead of entering them.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::read::MOUNTS)
+                .long("mounts")
+                .help("Annotate mount points with the filesystem mounted on them.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::detect::CONTENT)
+                .long("detect-type")
+                .help("Detect file types from their content and print them.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::detect::CATEGORIES)
+                .long("categories")
+                .num_args(1)
+                .help("Read file categories from the given TOML file instead of the user config.")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new(options::meta::META)
                 .long("meta")
@@ -141,6 +277,24 @@ pub fn tree_app() -> Command {
                 .help("Print all default entry's metadata.")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::meta::INODE)
+                .long("inodes")
+                .help("Print entry's inode number and mark repeated hard links.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::DEVICE)
+                .long("device")
+                .help("Print the ID of the device the entry resides on.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::LINKS)
+                .long("links")
+                .help("Print entry's hard-link count and mark repeated hard links.")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::meta::PERMISSION)
                 .long("permission")
@@ -148,6 +302,44 @@ pub fn tree_app() -> Command {
                 .help("Print entires attribute.")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::meta::OCTAL)
+                .long("octal-perms")
+                .help("Print entires attribute as octal digits.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::ACL)
+                .long("acl")
+                .help("Mark entries carrying a POSIX ACL with '+' after the permission, which it turns on.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::GIT)
+                .long("git")
+                .help("Print entry's git status: [M]odified, [S]taged, untracked [?], ignored [!] or conflicted [U].")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::GITLOG)
+                .long("git-log")
+                .help("Print the short hash, author and relative date of the last commit touching each entry.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::USER)
+                .long("user")
+                .short('u')
+                .help("Print the entry's owner, or its UID if the name cannot be resolved.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::GROUP)
+                .long("group")
+                .short('g')
+                .help("Print the entry's group, or its GID if the name cannot be resolved.")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::meta::BTIME)
                 .long("btime")
@@ -166,12 +358,94 @@ pub fn tree_app() -> Command {
                 .help("Print the date that the entry was last time accessed.")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::meta::CTIME)
+                .long("ctime")
+                .help("Print the date that the entry's status was last changed.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::time::STYLE)
+                .long("time-style")
+                .num_args(1)
+                .help("Print dates using a preset style.")
+                .value_parser(["default", "iso", "rfc3339", "relative"])
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new(options::time::FORMAT)
+                .long("timefmt")
+                .num_args(1)
+                .help("Print dates using a strftime format, e.g. \"%Y-%m-%d %H:%M:%S\".")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new(options::time::UTC)
+                .long("utc")
+                .help("Print dates in UTC instead of local time.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::time::NANOS)
+                .long("nanos")
+                .help("Print dates with nanosecond precision.")
+                .conflicts_with(options::time::FORMAT)
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::meta::SIZE)
                 .long("size")
                 .help("Print entires's size.")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::meta::DISKUSAGE)
+                .long("disk-usage")
+                .help("Print entry's allocated size on disk instead of its apparent size.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::XATTR)
+                .long("xattr")
+                .help("Print the names of entry's extended attributes.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::CONTEXT)
+                .long("context")
+                .help("Print entry's SELinux security context.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::meta::LINES)
+                .long("lines")
+                .help("Print line count of text files, summed up for directories.")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::digest::HASH)
+                .long("hash")
+                .num_args(1)
+                .help("Print the digest of each file's content.")
+                .value_parser(["sha256", "blake3", "xxh3"])
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new(options::digest::MANIFEST)
+                .long("manifest")
+                .num_args(1)
+                .help("Write a manifest of every file to the given path. It is sha256sum-compatible unless --hash picks another algorithm.")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new(options::digest::VERIFY)
+                .long("verify")
+                .num_args(1)
+                .help("Mark files as ok, modified, missing or extra against a saved manifest, hashed with the algorithm it names.")
+                .value_parser(clap::value_parser!(std::path::PathBuf))
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new(options::miscellaneous::LEVEL)
                 .long("level")
@@ -188,6 +462,12 @@ pub fn tree_app() -> Command {
                 .help("Print exhaustive report")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::report::CODE)
+                .long("code-stats")
+                .help("Print line count grouped by language after the report")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::branch::NOBRANCH)
                 .long("nobranch")
@@ -195,6 +475,30 @@ pub fn tree_app() -> Command {
                 .help("Discard branch's stick from the output")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::branch::CHARSET)
+                .long("charset")
+                .num_args(1)
+                .value_parser([
+                    PossibleValue::new("ascii"),
+                    // GNU tree spells it `utf-8`
+                    PossibleValue::new("utf8").alias("utf-8"),
+                    PossibleValue::new("heavy"),
+                    PossibleValue::new("rounded"),
+                    PossibleValue::new("double"),
+                ])
+                .help("Draw branches with the given character set.")
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new(options::branch::INDENT)
+                .long("indent")
+                .num_args(1)
+                .value_name("WIDTH")
+                .value_parser(clap::value_parser!(u8).range(2..=16))
+                .help("Indent each level by WIDTH columns, 4 by default.")
+                .action(ArgAction::Set),
+        )
 }
 
 #[cfg(test)]
@@ -235,4 +539,19 @@ mod tests {
 
         dbg!(matches);
     }
+
+    #[test]
+    fn test_charset_alias() {
+        for charset in ["utf8", "utf-8"] {
+            let matches = tree_app()
+                .try_get_matches_from(["tree-rs", "--charset", charset])
+                .unwrap();
+            let name = matches.get_one::<String>(options::branch::CHARSET).unwrap();
+            assert!(crate::tree::branch::Charset::from_name(name).is_some());
+        }
+
+        assert!(tree_app()
+            .try_get_matches_from(["tree-rs", "--charset", "latin1"])
+            .is_err());
+    }
 }
@@ -1,12 +1,23 @@
 use super::app::options;
 use super::app::tree_app;
 
+use clap::Command;
+
+use crate::config::category::Categories;
+use crate::config::color::ColorWhen;
 use crate::config::root::BaseDirectory;
+use crate::config::theme::Theme;
+use crate::config::timefmt::TimeStyle;
+use crate::render::quote::QuotingStyle;
 use crate::report::stats::ReportMode;
+use crate::tree::branch::Charset;
+use crate::walk::digest::HashAlgo;
+use crate::walk::mount::MountTable;
 use crate::walk::tr::TreeCtxt;
 
 use std::env;
 use std::ffi::OsString;
+use std::io::IsTerminal;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -94,14 +105,31 @@ impl TreeArgs {
             tr.rg.with_sort_by_file_first()?;
         }
 
-        if matches.get_flag(options::color::COLOR) {
-            tr.rg.with_entry()?;
-        }
-
         if matches.get_flag(options::path::RELATIVE) {
             tr.rg.with_relative_path()?;
         }
 
+        let quoting = if matches.get_flag(options::path::QUESTION) {
+            QuotingStyle::Question
+        } else if matches.get_flag(options::path::LITERAL) {
+            QuotingStyle::Literal
+        } else if let Some(style) = matches.get_one::<String>(options::path::QUOTING) {
+            QuotingStyle::from_name(style).expect("validated by clap")
+        } else if std::io::stdout().is_terminal() {
+            QuotingStyle::Question
+        } else {
+            QuotingStyle::Literal
+        };
+        tr.buf.with_quoting(quoting);
+
+        if matches.get_flag(options::path::HYPERLINK) {
+            tr.buf.with_hyperlinks();
+        }
+
+        if matches.get_flag(options::path::CLASSIFY) {
+            tr.rg.with_classify()?;
+        }
+
         // TODO
         // if matches.get_flag(options::path::ABSOLUTE) {}
 
@@ -117,10 +145,81 @@ impl TreeArgs {
             tr.rg.read_visible_folders()?;
         }
 
+        if matches.get_flag(options::read::FOLLOW) {
+            tr.follow.enable();
+        }
+
+        if matches.get_flag(options::read::ONEFS) {
+            tr.one_fs.enable();
+            tr.one_fs.with_root(&base_dir.base_path());
+        }
+
+        if matches.get_flag(options::read::MOUNTS) {
+            tr.mounts = MountTable::load();
+        }
+
+        let categories = match matches.get_one::<PathBuf>(options::detect::CATEGORIES) {
+            Some(path) => Categories::load(path)?,
+            None => Categories::from_user_config()?,
+        };
+        tr.with_categories(categories);
+
+        if matches.get_flag(options::detect::CONTENT) {
+            tr.rg.with_content_detection()?;
+        }
+
+        if matches.get_flag(options::meta::INODE) {
+            tr.rg.with_inode()?;
+            tr.hardlinks.enable();
+        }
+
+        if matches.get_flag(options::meta::DEVICE) {
+            tr.rg.with_device()?;
+        }
+
+        if matches.get_flag(options::meta::LINKS) {
+            tr.rg.with_links()?;
+            tr.hardlinks.enable();
+        }
+
         if matches.get_flag(options::meta::PERMISSION) {
             tr.rg.with_permission()?;
         }
 
+        if matches.get_flag(options::meta::OCTAL) {
+            tr.rg.with_octal_permission()?;
+        }
+
+        if matches.get_flag(options::meta::ACL) {
+            // The marker follows the mode, so show it when neither form was asked for.
+            if !matches.get_flag(options::meta::PERMISSION)
+                && !matches.get_flag(options::meta::OCTAL)
+            {
+                tr.rg.with_permission()?;
+            }
+            tr.acl.enable();
+        }
+
+        if matches.get_flag(options::meta::GIT) {
+            tr.rg.with_git()?;
+            tr.git.load(&base_dir.base_path())?;
+        }
+
+        if matches.get_flag(options::meta::GITLOG) {
+            tr.rg.with_git_log()?;
+            // The root's children are the first level shown.
+            let depth = usize::try_from(tr.level.remaining() + 1).unwrap_or(0);
+            tr.git_history.load(&base_dir.base_path(), depth)?;
+        }
+
+        if matches.get_flag(options::meta::USER) {
+            tr.rg.with_user()?;
+        }
+
+        if matches.get_flag(options::meta::GROUP) {
+            tr.rg.with_group()?;
+        }
+
         if matches.get_flag(options::meta::BTIME) {
             tr.rg.with_btime()?;
         }
@@ -133,15 +232,109 @@ impl TreeArgs {
             tr.rg.with_atime()?;
         }
 
+        if matches.get_flag(options::meta::CTIME) {
+            tr.rg.with_ctime()?;
+        }
+
+        if let Some(style) = matches.get_one::<String>(options::time::STYLE) {
+            let style = match style.as_str() {
+                "iso" => TimeStyle::Iso,
+                "rfc3339" => TimeStyle::Rfc3339,
+                "relative" => TimeStyle::Relative,
+                _ => TimeStyle::Default,
+            };
+            tr.timefmt.with_style(style);
+        }
+
+        if let Some(pattern) = matches.get_one::<String>(options::time::FORMAT) {
+            tr.timefmt.with_pattern(pattern)?;
+        }
+
+        if matches.get_flag(options::time::UTC) {
+            tr.timefmt.with_utc();
+        }
+
+        if matches.get_flag(options::time::NANOS) {
+            tr.timefmt.with_nanos()?;
+        }
+
         if matches.get_flag(options::meta::SIZE) {
             tr.rg.with_size()?;
         }
 
+        if matches.get_flag(options::meta::DISKUSAGE) {
+            tr.rg.with_disk_usage()?;
+            tr.dir_stats.with_disk_usage();
+        }
+
+        if matches.get_flag(options::meta::XATTR) {
+            tr.rg.with_xattr()?;
+        }
+
+        if matches.get_flag(options::meta::CONTEXT) {
+            tr.rg.with_context()?;
+        }
+
+        if matches.get_flag(options::meta::LINES) {
+            tr.rg.with_lines()?;
+            tr.line_counter.enable();
+        }
+
+        if matches.get_flag(options::report::CODE) {
+            tr.code_stats.enable();
+            tr.line_counter.enable();
+        }
+
+        if let Some(algo) = matches.get_one::<String>(options::digest::HASH) {
+            let algo = HashAlgo::from_name(algo).expect("validated by clap");
+            tr.digest.with_algo(algo);
+            tr.rg.with_hash()?;
+        }
+
+        if let Some(manifest) = matches.get_one::<PathBuf>(options::digest::MANIFEST) {
+            tr.digest.with_manifest(manifest)?;
+        }
+
+        if let Some(manifest) = matches.get_one::<PathBuf>(options::digest::VERIFY) {
+            let algo = matches
+                .get_one::<String>(options::digest::HASH)
+                .and_then(|algo| HashAlgo::from_name(algo))
+                .unwrap_or(HashAlgo::Sha256);
+            tr.verifier.with_manifest(manifest, algo)?;
+            tr.dir_stats.with_verify();
+        }
+
+        if matches.get_flag(options::color::ICONS) {
+            tr.rg.with_icons()?;
+        }
+
+        tr.theme = match matches.get_one::<PathBuf>(options::color::THEME) {
+            Some(path) => Theme::load(path)?,
+            None => Theme::from_user_config()?,
+        };
+        if let Some(glyphs) = tr.theme.branch() {
+            tr.branch.with_glyphs(glyphs);
+        }
+
+        let charset = matches.get_one::<String>(options::branch::CHARSET);
+        let indent = matches.get_one::<u8>(options::branch::INDENT);
+        if charset.is_some() || indent.is_some() {
+            let charset = charset
+                .and_then(|charset| Charset::from_name(charset))
+                .unwrap_or(Charset::Utf8);
+            tr.branch
+                .with_charset(charset, indent.map_or(4, |width| *width as usize));
+        }
+
         if matches.get_flag(options::branch::NOBRANCH) {
             tr.branch.no_branch();
         }
 
-        if matches.get_flag(options::color::COLORLESS) {
+        let when = matches
+            .get_one::<String>(options::color::COLOR)
+            .and_then(|when| ColorWhen::from_name(when))
+            .expect("validated by clap");
+        if matches.get_flag(options::color::COLORLESS) || !when.enabled() {
             tr.rg.with_no_color()?;
         }
 
@@ -154,8 +347,18 @@ impl TreeArgs {
 // since it maybe not the path we are looking for.
 fn extract_and_update_base_dir(args: &mut Vec<OsString>, base_dir: &mut BaseDirectory) -> bool {
     let mut delete_index = None;
+    let mut previous: Option<&OsString> = None;
+    let app = tree_app();
 
     for (index, arg) in args.iter().skip(1).enumerate() {
+        // Skip values of options such as `--theme <FILE>`.
+        if previous
+            .replace(arg)
+            .is_some_and(|flag| takes_value(&app, flag))
+        {
+            continue;
+        }
+
         if let Some(arg_path) = valid_path(arg) {
             base_dir.with_base_path(arg_path.clone());
             base_dir.with_filename(arg_path.into_os_string());
@@ -172,6 +375,28 @@ fn extract_and_update_base_dir(args: &mut Vec<OsString>, base_dir: &mut BaseDire
     }
 }
 
+/// Whether `flag` is an option expecting its value in the next argument.
+fn takes_value(app: &Command, flag: &OsString) -> bool {
+    let Some(flag) = flag.to_str() else {
+        return false;
+    };
+
+    let arg = if let Some(long) = flag.strip_prefix("--") {
+        app.get_arguments().find(|arg| arg.get_long() == Some(long))
+    } else if let Some(short) = flag
+        .strip_prefix('-')
+        .filter(|short| short.chars().count() == 1)
+    {
+        let short = short.chars().next();
+        app.get_arguments().find(|arg| arg.get_short() == short)
+    } else {
+        None
+    };
+
+    // Values of `--opt=VALUE` only options are never in the next argument.
+    arg.is_some_and(|arg| arg.get_action().takes_values() && !arg.is_require_equals_set())
+}
+
 fn valid_path(arg: &OsString) -> Option<PathBuf> {
     let path = Path::new(arg);
     if path.is_dir() || path.is_file() {
@@ -237,4 +462,32 @@ mod tests {
         let tree_args = TreeArgs { args };
         assert!(tree_args.assert_single_path().is_none());
     }
+
+    #[test]
+    fn test_skip_option_values() {
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let theme = temp_dir.path().join("theme.toml");
+        File::create(&theme).expect("Failed to create theme");
+
+        let mut args = vec![
+            OsString::from("trees-rs"),
+            OsString::from("--theme"),
+            OsString::from(&theme),
+            OsString::from(temp_dir.path()),
+        ];
+        let mut base_dir = BaseDirectory::from_current_dir().unwrap();
+
+        assert!(extract_and_update_base_dir(&mut args, &mut base_dir));
+        assert_eq!(base_dir.base_path(), temp_dir.path());
+        assert_eq!(args.len(), 3);
+
+        let mut args = vec![
+            OsString::from("trees-rs"),
+            OsString::from("--verify"),
+            OsString::from(&theme),
+            OsString::from(temp_dir.path()),
+        ];
+        assert!(extract_and_update_base_dir(&mut args, &mut base_dir));
+        assert_eq!(args[2], OsString::from(&theme));
+    }
 }
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::fs;
+
+static PASSWD: &str = "/etc/passwd";
+static GROUP: &str = "/etc/group";
+
+/// Resolves numeric user and group IDs into names.
+///
+/// The databases are only read on the first lookup and every resolved ID
+/// is kept around, so large trees owned by a handful of accounts stay cheap.
+#[derive(Debug, Default, Clone)]
+pub struct Accounts {
+    users: Option<HashMap<u32, String>>,
+    groups: Option<HashMap<u32, String>>,
+}
+
+impl Accounts {
+    /// Returns the user name for `uid`, or the numeric ID if it cannot be resolved.
+    pub fn user_name(&mut self, uid: u32) -> String {
+        let users = self.users.get_or_insert_with(|| load_database(PASSWD));
+        users.get(&uid).cloned().unwrap_or_else(|| uid.to_string())
+    }
+
+    /// Returns the group name for `gid`, or the numeric ID if it cannot be resolved.
+    pub fn group_name(&mut self, gid: u32) -> String {
+        let groups = self.groups.get_or_insert_with(|| load_database(GROUP));
+        groups.get(&gid).cloned().unwrap_or_else(|| gid.to_string())
+    }
+}
+
+fn load_database(path: &str) -> HashMap<u32, String> {
+    fs::read_to_string(path)
+        .map(|content| parse_database(&content))
+        .unwrap_or_default()
+}
+
+/// Parses `/etc/passwd` or `/etc/group` formatted content, where the first
+/// field is the name and the third field is the numeric ID.
+fn parse_database(content: &str) -> HashMap<u32, String> {
+    let mut ids = HashMap::new();
+
+    for line in content.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split(':');
+        let (Some(name), Some(_), Some(id)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+
+        if let Ok(id) = id.parse::<u32>() {
+            // Keep the first entry like getpwuid(3) does.
+            ids.entry(id).or_insert_with(|| name.to_string());
+        }
+    }
+
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_database() {
+        let content = "\
+# comment
+root:x:0:0:root:/root:/bin/bash
+daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin
+toor:x:0:0:root:/root:/bin/sh
+broken line
+nobody:x:65534:65534:nobody:/nonexistent:/usr/sbin/nologin";
+
+        let ids = parse_database(content);
+
+        assert_eq!(ids.get(&0).map(String::as_str), Some("root"));
+        assert_eq!(ids.get(&1).map(String::as_str), Some("daemon"));
+        assert_eq!(ids.get(&65534).map(String::as_str), Some("nobody"));
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn test_fallback_to_numeric_id() {
+        let mut accounts = Accounts {
+            users: Some(HashMap::new()),
+            groups: Some(HashMap::new()),
+        };
+
+        assert_eq!(accounts.user_name(4242), "4242");
+        assert_eq!(accounts.group_name(4343), "4343");
+    }
+}
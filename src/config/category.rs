@@ -0,0 +1,229 @@
+use super::file::existing_config_file;
+use super::theme::to_sgr;
+
+use serde::Deserialize;
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+static CATEGORIES_FILE: &str = "categories.toml";
+
+#[rustfmt::skip]
+static BUILTIN: &[(&str, &[&str])] = &[
+    ("image",      &["jpg", "jpeg", "png", "gif", "bmp", "tiff", "webp", "svg", "ico", "heic"]),
+    ("video",      &["mp4", "avi", "mkv", "mov", "flv", "wmv", "webm", "m4v"]),
+    ("audio",      &["mp3", "wav", "ogg", "flac", "aac", "m4a", "opus"]),
+    ("archive",    &["zip", "tar", "gz", "tgz", "bz2", "xz", "zst", "7z", "rar"]),
+    ("source",     &["rs", "c", "h", "cc", "cpp", "hpp", "py", "js", "ts", "go", "java",
+                     "kt", "rb", "sh", "swift", "cs", "php", "lua", "zig", "hs", "scala"]),
+    ("document",   &["pdf", "doc", "docx", "odt", "txt", "md", "rst", "tex", "epub",
+                     "xls", "xlsx", "ppt", "pptx", "csv"]),
+    ("executable", &["exe", "dll", "so", "dylib", "bin", "appimage", "wasm"]),
+];
+
+#[derive(Debug, Clone)]
+pub struct Category {
+    name: String,
+    extensions: HashSet<String>,
+    /// SGR parameters from the `color` key, if the category defines one.
+    color: Option<String>,
+}
+
+impl Category {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn color(&self) -> Option<&str> {
+        self.color.as_deref()
+    }
+}
+
+/// File categories used for colouring and for the per-category report.
+///
+/// Built-in categories can be overridden and new ones appended through
+/// `categories.toml` in the configuration directory:
+///
+/// ```toml
+/// [[category]]
+/// name = "image"
+/// extensions = ["png", "jpg", "avif"]
+///
+/// [[category]]
+/// name = "model"
+/// extensions = ["onnx", "safetensors"]
+/// color = "bold magenta"
+/// ```
+#[derive(Debug, Clone)]
+pub struct Categories {
+    list: Vec<Category>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CategoriesFile {
+    #[serde(default)]
+    category: Vec<CategoryEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CategoryEntry {
+    name: String,
+    extensions: Vec<String>,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+impl Default for Categories {
+    fn default() -> Self {
+        let list = BUILTIN
+            .iter()
+            .map(|(name, extensions)| Category {
+                name: name.to_string(),
+                extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+                color: None,
+            })
+            .collect();
+
+        Categories { list }
+    }
+}
+
+impl Categories {
+    /// Built-in categories merged with the user's `categories.toml`, if any.
+    pub fn from_user_config() -> anyhow::Result<Self> {
+        match existing_config_file(CATEGORIES_FILE) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Built-in categories merged with the ones defined in `path`.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read categories from '{}'", path.display()))?;
+
+        let mut categories = Self::default();
+        categories
+            .merge(&content)
+            .with_context(|| format!("Invalid categories in '{}'", path.display()))?;
+        Ok(categories)
+    }
+
+    /// Categories with an existing name replace its extensions, new ones are appended.
+    fn merge(&mut self, content: &str) -> anyhow::Result<()> {
+        let file: CategoriesFile = toml::from_str(content)?;
+
+        for entry in file.category {
+            let extensions = entry
+                .extensions
+                .iter()
+                .map(|ext| ext.trim_start_matches('.').to_lowercase())
+                .collect();
+
+            let color = match entry.color {
+                Some(color) => Some(to_sgr(&color).ok_or_else(|| {
+                    anyhow::anyhow!("Unknown colour '{color}' for category '{}'", entry.name)
+                })?),
+                None => None,
+            };
+
+            match self.list.iter_mut().find(|c| c.name == entry.name) {
+                Some(category) => {
+                    category.extensions = extensions;
+                    category.color = color.or(category.color.take());
+                }
+                None => self.list.push(Category {
+                    name: entry.name,
+                    extensions,
+                    color,
+                }),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Category> {
+        self.list.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Category> {
+        self.list.get(index)
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<usize> {
+        self.list.iter().position(|category| category.name == name)
+    }
+
+    pub fn by_path(&self, path: &Path) -> Option<usize> {
+        let ext = path.extension().and_then(OsStr::to_str)?.to_lowercase();
+        self.list
+            .iter()
+            .position(|category| category.extensions.contains(&ext))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_by_path() {
+        let categories = Categories::default();
+
+        let image = categories.by_path(Path::new("photo.JPG")).unwrap();
+        assert_eq!(categories.get(image).unwrap().name(), "image");
+
+        let source = categories.by_path(Path::new("src/main.rs")).unwrap();
+        assert_eq!(categories.get(source).unwrap().name(), "source");
+
+        assert_eq!(categories.by_path(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn test_merge_user_categories() {
+        let mut categories = Categories::default();
+        let builtin_len = categories.iter().count();
+
+        categories
+            .merge(
+                r#"
+                [[category]]
+                name = "image"
+                extensions = [".avif"]
+
+                [[category]]
+                name = "model"
+                extensions = ["onnx", "SafeTensors"]
+                color = "bold magenta"
+                "#,
+            )
+            .unwrap();
+
+        assert_eq!(categories.iter().count(), builtin_len + 1);
+        assert_eq!(categories.by_path(Path::new("a.png")), None);
+        assert_eq!(
+            categories.by_path(Path::new("a.avif")),
+            categories.by_name("image")
+        );
+        assert_eq!(
+            categories.by_path(Path::new("weights.safetensors")),
+            categories.by_name("model")
+        );
+        let model = categories.by_name("model").unwrap();
+        assert_eq!(categories.get(model).unwrap().color(), Some("1;35"));
+    }
+
+    #[test]
+    fn test_merge_rejects_invalid_file() {
+        let mut categories = Categories::default();
+        assert!(categories.merge("[[category]]\nname = 1").is_err());
+        assert!(categories
+            .merge("[[category]]\nname = \"a\"\nextensions = []\ncolor = \"nope\"")
+            .is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use std::env;
+use std::ffi::OsString;
+use std::io;
+use std::io::IsTerminal;
+
+/// When to emit colours, as given to `--color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorWhen {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorWhen {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(ColorWhen::Auto),
+            "always" => Some(ColorWhen::Always),
+            "never" => Some(ColorWhen::Never),
+            _ => None,
+        }
+    }
+
+    /// Whether stdout should be coloured, honouring `NO_COLOR` and
+    /// `CLICOLOR_FORCE` in `auto` mode.
+    pub fn enabled(self) -> bool {
+        self.resolve(
+            io::stdout().is_terminal(),
+            env::var_os("NO_COLOR"),
+            env::var_os("CLICOLOR_FORCE"),
+        )
+    }
+
+    fn resolve(
+        self,
+        is_terminal: bool,
+        no_color: Option<OsString>,
+        force: Option<OsString>,
+    ) -> bool {
+        match self {
+            ColorWhen::Always => true,
+            ColorWhen::Never => false,
+            ColorWhen::Auto => {
+                // Both variables only count when set to a meaningful value.
+                if no_color.is_some_and(|value| !value.is_empty()) {
+                    false
+                } else if force.is_some_and(|value| !value.is_empty() && value != "0") {
+                    true
+                } else {
+                    is_terminal
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let set = |value: &str| Some(OsString::from(value));
+
+        assert!(ColorWhen::Auto.resolve(true, None, None));
+        assert!(!ColorWhen::Auto.resolve(false, None, None));
+        assert!(!ColorWhen::Auto.resolve(true, set("1"), None));
+        assert!(ColorWhen::Auto.resolve(true, set(""), None));
+        assert!(ColorWhen::Auto.resolve(false, None, set("1")));
+        assert!(!ColorWhen::Auto.resolve(false, None, set("0")));
+        assert!(!ColorWhen::Auto.resolve(false, set("1"), set("1")));
+        assert!(ColorWhen::Always.resolve(false, set("1"), None));
+        assert!(!ColorWhen::Never.resolve(true, None, set("1")));
+    }
+}
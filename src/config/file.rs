@@ -0,0 +1,20 @@
+use std::env;
+use std::path::PathBuf;
+
+/// Directory holding trees-rs configuration files, i.e.
+/// `$XDG_CONFIG_HOME/trees-rs` or `~/.config/trees-rs`.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(base.join("trees-rs"))
+}
+
+/// Path of `name` inside the configuration directory, if that file exists.
+pub fn existing_config_file(name: &str) -> Option<PathBuf> {
+    config_dir()
+        .map(|dir| dir.join(name))
+        .filter(|path| path.is_file())
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::fs::Metadata;
+use std::path::Path;
+
+static LS_COLORS: &str = "LS_COLORS";
+
+/// Entry styles read from `LS_COLORS`, as produced by `dircolors`.
+///
+/// Each style is the SGR parameter list of an escape sequence, e.g. `01;34`.
+/// Without `LS_COLORS` nothing resolves and the built-in palette is used.
+#[derive(Debug, Default, Clone)]
+pub struct LsColors {
+    /// Two-letter keys such as `di`, `ln` or `ex`.
+    types: HashMap<String, String>,
+    /// File name suffixes from `*.ext` keys, lowercased, in definition order.
+    suffixes: Vec<(String, String)>,
+}
+
+impl LsColors {
+    pub fn from_env() -> Self {
+        env::var(LS_COLORS)
+            .map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Self {
+        let mut colors = Self::default();
+
+        for (key, style) in value.split(':').filter_map(|entry| entry.split_once('=')) {
+            if let Some(suffix) = key.strip_prefix('*') {
+                colors
+                    .suffixes
+                    .push((suffix.to_lowercase(), style.to_string()));
+            } else {
+                colors.types.insert(key.to_string(), style.to_string());
+            }
+        }
+
+        colors
+    }
+
+    /// Style of the entry at `path`, whose metadata does not follow symlinks.
+    pub fn style(&self, path: &Path, meta: &Metadata) -> Option<&str> {
+        let key = if meta.is_symlink() {
+            match fs::metadata(path) {
+                Err(_) => "or",
+                // `ln=target` colours links like the entry they point to.
+                Ok(target) if self.type_style("ln") == Some("target") => {
+                    return self.style(path, &target);
+                }
+                Ok(_) => "ln",
+            }
+        } else if meta.is_dir() {
+            dir_key(meta)
+        } else if meta.is_file() {
+            file_key(meta)
+        } else {
+            special_key(meta)
+        };
+
+        match key {
+            "fi" => self.suffix_style(path).or_else(|| self.type_style("fi")),
+            "or" => self.type_style("or").or_else(|| self.type_style("ln")),
+            key => self.type_style(key).or_else(|| {
+                // Unset specialised keys fall back to the plain type.
+                match key {
+                    "tw" | "ow" | "st" => self.type_style("di"),
+                    "su" | "sg" | "ex" | "mh" => {
+                        self.suffix_style(path).or_else(|| self.type_style("fi"))
+                    }
+                    _ => None,
+                }
+            }),
+        }
+    }
+
+    fn type_style(&self, key: &str) -> Option<&str> {
+        self.types
+            .get(key)
+            .map(String::as_str)
+            .filter(|style| !style.is_empty())
+    }
+
+    /// Later definitions win, like `ls`.
+    fn suffix_style(&self, path: &Path) -> Option<&str> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+
+        self.suffixes
+            .iter()
+            .rev()
+            .find(|(suffix, _)| name.ends_with(suffix.as_str()))
+            .map(|(_, style)| style.as_str())
+    }
+}
+
+#[cfg(unix)]
+fn dir_key(meta: &Metadata) -> &'static str {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = meta.permissions().mode();
+    let sticky = mode & 0o1000 != 0;
+    let other_writable = mode & 0o002 != 0;
+
+    match (sticky, other_writable) {
+        (true, true) => "tw",
+        (false, true) => "ow",
+        (true, false) => "st",
+        (false, false) => "di",
+    }
+}
+
+#[cfg(not(unix))]
+fn dir_key(_meta: &Metadata) -> &'static str {
+    "di"
+}
+
+#[cfg(unix)]
+fn file_key(meta: &Metadata) -> &'static str {
+    use std::os::unix::fs::MetadataExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = meta.permissions().mode();
+
+    if mode & 0o4000 != 0 {
+        "su"
+    } else if mode & 0o2000 != 0 {
+        "sg"
+    } else if mode & 0o111 != 0 {
+        "ex"
+    } else if meta.nlink() > 1 {
+        "mh"
+    } else {
+        "fi"
+    }
+}
+
+#[cfg(not(unix))]
+fn file_key(_meta: &Metadata) -> &'static str {
+    "fi"
+}
+
+#[cfg(unix)]
+fn special_key(meta: &Metadata) -> &'static str {
+    use std::os::unix::fs::FileTypeExt;
+
+    let file_type = meta.file_type();
+
+    if file_type.is_fifo() {
+        "pi"
+    } else if file_type.is_socket() {
+        "so"
+    } else if file_type.is_block_device() {
+        "bd"
+    } else if file_type.is_char_device() {
+        "cd"
+    } else {
+        "no"
+    }
+}
+
+#[cfg(not(unix))]
+fn special_key(_meta: &Metadata) -> &'static str {
+    "no"
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_resolve_styles() {
+        let dir = tempfile::tempdir().unwrap();
+        let colors = LsColors::parse("di=01;34:ln=01;36:or=40;31:ex=01;32:*.tar=01;31:*.TXT=35");
+
+        let archive = dir.path().join("a.TAR");
+        fs::write(&archive, b"").unwrap();
+        let text = dir.path().join("b.txt");
+        fs::write(&text, b"").unwrap();
+        let script = dir.path().join("run.tar");
+        fs::write(&script, b"").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let orphan = dir.path().join("orphan");
+        std::os::unix::fs::symlink(dir.path().join("nowhere"), &orphan).unwrap();
+        let plain = dir.path().join("plain");
+        fs::write(&plain, b"").unwrap();
+
+        let style = |path: &Path| colors.style(path, &fs::symlink_metadata(path).unwrap());
+        assert_eq!(style(dir.path()), Some("01;34"));
+        assert_eq!(style(&archive), Some("01;31"));
+        assert_eq!(style(&text), Some("35"));
+        assert_eq!(style(&script), Some("01;32"));
+        assert_eq!(style(&orphan), Some("40;31"));
+        assert_eq!(style(&plain), None);
+    }
+}
@@ -1,4 +1,11 @@
+pub mod accounts;
+pub mod category;
+pub mod color;
+pub mod file;
 pub mod inspect;
+pub mod lscolors;
 pub mod registry;
 pub mod root;
 pub mod sorting;
+pub mod theme;
+pub mod timefmt;
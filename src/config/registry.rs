@@ -1,3 +1,4 @@
+use super::category::Categories;
 use super::inspect::read_all_entries;
 use super::inspect::read_visible_entries;
 use super::inspect::read_visible_folders;
@@ -9,16 +10,36 @@ use super::sorting::FnSortEntries;
 
 use crate::render::attr::atime::FnExtAccessTime;
 use crate::render::attr::btime::FnExtBTime;
+use crate::render::attr::ctime::FnExtChangeTime;
+use crate::render::attr::device::FnExtDevice;
+use crate::render::attr::ftype::FnExtFileType;
+use crate::render::attr::git::FnExtGit;
+use crate::render::attr::gitlog::FnExtGitLog;
+use crate::render::attr::group::FnExtGroup;
+use crate::render::attr::hash::FnExtHash;
+use crate::render::attr::inode::FnExtInode;
+use crate::render::attr::lines::FnExtLines;
+use crate::render::attr::links::FnExtLinks;
 use crate::render::attr::mtime::FnExtModTime;
 use crate::render::attr::pms::FnExtPermission;
 use crate::render::attr::size::FnExtSize;
+use crate::render::attr::user::FnExtUser;
+use crate::render::attr::xattr::FnExtContext;
+use crate::render::attr::xattr::FnExtXattr;
 use crate::render::buffer::Buffer;
 use crate::render::color::FnColor;
+use crate::render::color::FnPaint;
+use crate::render::entree::classify::FnOutIndicator;
 use crate::render::entree::dirr::FnOutDir;
 use crate::render::entree::filee::FnOutFile;
 use crate::render::entree::headd::FnOutHead;
 use crate::render::entree::symlinked::FnOutSymlink;
+use crate::render::icon::FnIcon;
 use crate::report::stats::DirectoryStats;
+use crate::walk::magic::classify_by_content;
+use crate::walk::magic::classify_by_extension;
+use crate::walk::magic::FnClassify;
+use crate::walk::visit::Visitor;
 
 use std::fs::DirEntry;
 use std::io;
@@ -31,19 +52,35 @@ pub struct Registry<'a> {
     // Common util
     read: FnReadDir,
     sort: FnSortEntries,
+    classify: FnClassify,
 
     // Entry
     pub dir: FnOutDir<StdoutLock<'a>>,
     pub file: FnOutFile<StdoutLock<'a>>,
     pub symlink: FnOutSymlink<StdoutLock<'a>>,
     pub head: FnOutHead<StdoutLock<'a>>,
+    pub icon: FnIcon<StdoutLock<'a>>,
+    pub indicator: FnOutIndicator<StdoutLock<'a>>,
 
     // Metadata
+    pub inode: FnExtInode<StdoutLock<'a>>,
+    pub device: FnExtDevice<StdoutLock<'a>>,
     pub pms: FnExtPermission<StdoutLock<'a>>,
+    pub git: FnExtGit<StdoutLock<'a>>,
+    pub git_log: FnExtGitLog<StdoutLock<'a>>,
+    pub links: FnExtLinks<StdoutLock<'a>>,
+    pub user: FnExtUser<StdoutLock<'a>>,
+    pub group: FnExtGroup<StdoutLock<'a>>,
     pub btime: FnExtBTime<StdoutLock<'a>>,
     pub mtime: FnExtModTime<StdoutLock<'a>>,
     pub atime: FnExtAccessTime<StdoutLock<'a>>,
+    pub ctime: FnExtChangeTime<StdoutLock<'a>>,
     pub size: FnExtSize<StdoutLock<'a>>,
+    pub hash: FnExtHash<StdoutLock<'a>>,
+    pub ftype: FnExtFileType<StdoutLock<'a>>,
+    pub lines: FnExtLines<StdoutLock<'a>>,
+    pub xattr: FnExtXattr<StdoutLock<'a>>,
+    pub context: FnExtContext<StdoutLock<'a>>,
 
     // Color
     reset: FnColor<StdoutLock<'a>>,
@@ -53,6 +90,11 @@ pub struct Registry<'a> {
     blue: FnColor<StdoutLock<'a>>,
     green: FnColor<StdoutLock<'a>>,
     purple: FnColor<StdoutLock<'a>>,
+    red: FnColor<StdoutLock<'a>>,
+    bold_green: FnColor<StdoutLock<'a>>,
+    cyan: FnColor<StdoutLock<'a>>,
+    orphan: FnColor<StdoutLock<'a>>,
+    paint: FnPaint<StdoutLock<'a>>,
 }
 
 impl<'a> Registry<'a> {
@@ -83,6 +125,26 @@ impl<'a> Registry<'a> {
     pub fn purple(&self, buf: &mut Buffer<StdoutLock<'a>>) -> io::Result<()> {
         (self.purple)(buf)
     }
+
+    pub fn red(&self, buf: &mut Buffer<StdoutLock<'a>>) -> io::Result<()> {
+        (self.red)(buf)
+    }
+
+    pub fn bold_green(&self, buf: &mut Buffer<StdoutLock<'a>>) -> io::Result<()> {
+        (self.bold_green)(buf)
+    }
+
+    pub fn cyan(&self, buf: &mut Buffer<StdoutLock<'a>>) -> io::Result<()> {
+        (self.cyan)(buf)
+    }
+
+    pub fn orphan(&self, buf: &mut Buffer<StdoutLock<'a>>) -> io::Result<()> {
+        (self.orphan)(buf)
+    }
+
+    pub fn paint(&self, buf: &mut Buffer<StdoutLock<'a>>, style: &str) -> io::Result<()> {
+        (self.paint)(buf, style)
+    }
 }
 
 impl<'a> Registry<'a> {
@@ -97,6 +159,10 @@ impl<'a> Registry<'a> {
     pub fn sort_dents(&self, entries: &mut Vec<DirEntry>) {
         (self.sort)(entries)
     }
+
+    pub fn classify(&self, visitor: &mut Visitor, categories: &Categories) {
+        (self.classify)(visitor, categories)
+    }
 }
 
 impl<'a> Registry<'a> {
@@ -104,19 +170,35 @@ impl<'a> Registry<'a> {
         // Common util
         let read: FnReadDir = read_visible_entries;
         let sort: FnSortEntries = sort_by_name;
+        let classify: FnClassify = classify_by_extension;
 
         // Entry
         let dir: FnOutDir<StdoutLock> = Buffer::write_dir;
         let file: FnOutFile<StdoutLock> = Buffer::write_entry;
         let head: FnOutHead<StdoutLock> = Buffer::write_header_name;
         let symlink: FnOutSymlink<StdoutLock> = Buffer::write_symlink;
+        let icon: FnIcon<StdoutLock> = Buffer::write_no_icon;
+        let indicator: FnOutIndicator<StdoutLock> = Buffer::write_no_indicator;
 
         // Entry's metadata
+        let inode: FnExtInode<StdoutLock> = Buffer::write_no_inode;
+        let device: FnExtDevice<StdoutLock> = Buffer::write_no_device;
         let pms: FnExtPermission<StdoutLock> = Buffer::write_no_permission;
+        let git: FnExtGit<StdoutLock> = Buffer::write_no_git;
+        let git_log: FnExtGitLog<StdoutLock> = Buffer::write_no_git_log;
+        let links: FnExtLinks<StdoutLock> = Buffer::write_no_links;
+        let user: FnExtUser<StdoutLock> = Buffer::write_no_user;
+        let group: FnExtGroup<StdoutLock> = Buffer::write_no_group;
         let btime: FnExtBTime<StdoutLock> = Buffer::write_no_btime;
         let mtime: FnExtModTime<StdoutLock> = Buffer::write_no_mtime;
         let atime: FnExtAccessTime<StdoutLock> = Buffer::write_no_atime;
+        let ctime: FnExtChangeTime<StdoutLock> = Buffer::write_no_ctime;
         let size: FnExtSize<StdoutLock> = Buffer::write_no_size;
+        let hash: FnExtHash<StdoutLock> = Buffer::write_no_hash;
+        let ftype: FnExtFileType<StdoutLock> = Buffer::write_no_file_type;
+        let lines: FnExtLines<StdoutLock> = Buffer::write_no_lines;
+        let xattr: FnExtXattr<StdoutLock> = Buffer::write_no_xattr;
+        let context: FnExtContext<StdoutLock> = Buffer::write_no_context;
 
         // Color
         let reset: FnColor<StdoutLock> = Buffer::reset_color;
@@ -126,22 +208,43 @@ impl<'a> Registry<'a> {
         let blue: FnColor<StdoutLock> = Buffer::blue;
         let green: FnColor<StdoutLock> = Buffer::green;
         let purple: FnColor<StdoutLock> = Buffer::purple;
+        let red: FnColor<StdoutLock> = Buffer::red;
+        let bold_green: FnColor<StdoutLock> = Buffer::bold_green;
+        let cyan: FnColor<StdoutLock> = Buffer::cyan;
+        let orphan: FnColor<StdoutLock> = Buffer::orphan;
+        let paint: FnPaint<StdoutLock> = Buffer::paint;
 
         Ok(Self {
             // common-util
             read,
             sort,
+            classify,
             // entry
             dir,
             file,
             head,
             symlink,
+            icon,
+            indicator,
             // pms
+            inode,
+            device,
             pms,
+            git,
+            git_log,
+            links,
+            user,
+            group,
             btime,
             mtime,
             atime,
+            ctime,
             size,
+            hash,
+            ftype,
+            lines,
+            xattr,
+            context,
             // color
             reset,
             yellow,
@@ -150,6 +253,11 @@ impl<'a> Registry<'a> {
             blue,
             green,
             purple,
+            red,
+            bold_green,
+            cyan,
+            orphan,
+            paint,
         })
     }
 }
@@ -197,6 +305,23 @@ impl<'a> Registry<'a> {
     }
 }
 
+// Classification
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_extension_detection(&mut self) -> anyhow::Result<()> {
+        self.classify = classify_by_extension;
+        self.ftype = Buffer::write_no_file_type;
+        Ok(())
+    }
+
+    /// Sniff magic bytes to classify files and show the detected type.
+    pub fn with_content_detection(&mut self) -> anyhow::Result<()> {
+        self.classify = classify_by_content;
+        self.ftype = Buffer::write_file_type;
+        Ok(())
+    }
+}
+
 // Permission
 #[allow(dead_code)]
 impl<'a> Registry<'a> {
@@ -205,12 +330,115 @@ impl<'a> Registry<'a> {
         Ok(())
     }
 
+    pub fn with_octal_permission(&mut self) -> anyhow::Result<()> {
+        self.pms = Buffer::write_octal_permission;
+        Ok(())
+    }
+
     pub fn with_no_permission(&mut self) -> anyhow::Result<()> {
         self.pms = Buffer::write_no_permission;
         Ok(())
     }
 }
 
+// Git status
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_git(&mut self) -> anyhow::Result<()> {
+        self.git = Buffer::write_git;
+        Ok(())
+    }
+
+    pub fn with_no_git(&mut self) -> anyhow::Result<()> {
+        self.git = Buffer::write_no_git;
+        Ok(())
+    }
+}
+
+// Last commit
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_git_log(&mut self) -> anyhow::Result<()> {
+        self.git_log = Buffer::write_git_log;
+        Ok(())
+    }
+
+    pub fn with_no_git_log(&mut self) -> anyhow::Result<()> {
+        self.git_log = Buffer::write_no_git_log;
+        Ok(())
+    }
+}
+
+// Inode
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_inode(&mut self) -> anyhow::Result<()> {
+        self.inode = Buffer::write_inode;
+        Ok(())
+    }
+
+    pub fn with_no_inode(&mut self) -> anyhow::Result<()> {
+        self.inode = Buffer::write_no_inode;
+        Ok(())
+    }
+}
+
+// Device
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_device(&mut self) -> anyhow::Result<()> {
+        self.device = Buffer::write_device;
+        Ok(())
+    }
+
+    pub fn with_no_device(&mut self) -> anyhow::Result<()> {
+        self.device = Buffer::write_no_device;
+        Ok(())
+    }
+}
+
+// Hard-link count
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_links(&mut self) -> anyhow::Result<()> {
+        self.links = Buffer::write_links;
+        Ok(())
+    }
+
+    pub fn with_no_links(&mut self) -> anyhow::Result<()> {
+        self.links = Buffer::write_no_links;
+        Ok(())
+    }
+}
+
+// Owner
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_user(&mut self) -> anyhow::Result<()> {
+        self.user = Buffer::write_user;
+        Ok(())
+    }
+
+    pub fn with_no_user(&mut self) -> anyhow::Result<()> {
+        self.user = Buffer::write_no_user;
+        Ok(())
+    }
+}
+
+// Group
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_group(&mut self) -> anyhow::Result<()> {
+        self.group = Buffer::write_group;
+        Ok(())
+    }
+
+    pub fn with_no_group(&mut self) -> anyhow::Result<()> {
+        self.group = Buffer::write_no_group;
+        Ok(())
+    }
+}
+
 // Read entry's btime.
 #[allow(dead_code)]
 impl<'a> Registry<'a> {
@@ -253,7 +481,22 @@ impl<'a> Registry<'a> {
     }
 }
 
+// Read ctime
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_ctime(&mut self) -> anyhow::Result<()> {
+        self.ctime = Buffer::write_ctime;
+        Ok(())
+    }
+
+    pub fn with_no_ctime(&mut self) -> anyhow::Result<()> {
+        self.ctime = Buffer::write_no_ctime;
+        Ok(())
+    }
+}
+
 // Kind's entry
+#[allow(dead_code)]
 impl<'a> Registry<'a> {
     pub fn with_entry(&mut self) -> anyhow::Result<()> {
         self.dir = Buffer::write_dir;
@@ -269,6 +512,34 @@ impl<'a> Registry<'a> {
     }
 }
 
+// Classify
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_classify(&mut self) -> anyhow::Result<()> {
+        self.indicator = Buffer::write_indicator;
+        Ok(())
+    }
+
+    pub fn with_no_classify(&mut self) -> anyhow::Result<()> {
+        self.indicator = Buffer::write_no_indicator;
+        Ok(())
+    }
+}
+
+// Icons
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_icons(&mut self) -> anyhow::Result<()> {
+        self.icon = Buffer::write_icon;
+        Ok(())
+    }
+
+    pub fn with_no_icons(&mut self) -> anyhow::Result<()> {
+        self.icon = Buffer::write_no_icon;
+        Ok(())
+    }
+}
+
 // Size
 #[allow(dead_code)]
 impl<'a> Registry<'a> {
@@ -277,12 +548,73 @@ impl<'a> Registry<'a> {
         Ok(())
     }
 
+    pub fn with_disk_usage(&mut self) -> anyhow::Result<()> {
+        self.size = Buffer::write_disk_usage;
+        Ok(())
+    }
+
     pub fn with_no_size(&mut self) -> anyhow::Result<()> {
         self.size = Buffer::write_no_size;
         Ok(())
     }
 }
 
+// Content digest
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_hash(&mut self) -> anyhow::Result<()> {
+        self.hash = Buffer::write_hash;
+        Ok(())
+    }
+
+    pub fn with_no_hash(&mut self) -> anyhow::Result<()> {
+        self.hash = Buffer::write_no_hash;
+        Ok(())
+    }
+}
+
+// Line count
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_lines(&mut self) -> anyhow::Result<()> {
+        self.lines = Buffer::write_lines;
+        Ok(())
+    }
+
+    pub fn with_no_lines(&mut self) -> anyhow::Result<()> {
+        self.lines = Buffer::write_no_lines;
+        Ok(())
+    }
+}
+
+// Extended attributes
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_xattr(&mut self) -> anyhow::Result<()> {
+        self.xattr = Buffer::write_xattr;
+        Ok(())
+    }
+
+    pub fn with_no_xattr(&mut self) -> anyhow::Result<()> {
+        self.xattr = Buffer::write_no_xattr;
+        Ok(())
+    }
+}
+
+// SELinux context
+#[allow(dead_code)]
+impl<'a> Registry<'a> {
+    pub fn with_context(&mut self) -> anyhow::Result<()> {
+        self.context = Buffer::write_context;
+        Ok(())
+    }
+
+    pub fn with_no_context(&mut self) -> anyhow::Result<()> {
+        self.context = Buffer::write_no_context;
+        Ok(())
+    }
+}
+
 #[allow(dead_code)]
 impl<'a> Registry<'a> {
     pub fn with_color(&mut self) -> anyhow::Result<()> {
@@ -292,6 +624,12 @@ impl<'a> Registry<'a> {
         self.underlined_blue = Buffer::underlined_blue;
         self.blue = Buffer::blue;
         self.green = Buffer::green;
+        self.purple = Buffer::purple;
+        self.red = Buffer::red;
+        self.bold_green = Buffer::bold_green;
+        self.cyan = Buffer::cyan;
+        self.orphan = Buffer::orphan;
+        self.paint = Buffer::paint;
         Ok(())
     }
 
@@ -303,6 +641,11 @@ impl<'a> Registry<'a> {
         self.blue = Buffer::no_color;
         self.green = Buffer::no_color;
         self.purple = Buffer::no_color;
+        self.red = Buffer::no_color;
+        self.bold_green = Buffer::no_color;
+        self.cyan = Buffer::no_color;
+        self.orphan = Buffer::no_color;
+        self.paint = Buffer::no_paint;
         Ok(())
     }
 }
@@ -16,8 +16,8 @@ pub fn reverse_sort_by_name(vector: &mut Vec<fs::DirEntry>) {
 #[allow(dead_code)]
 pub fn sort_by_file_first(vector: &mut Vec<fs::DirEntry>) {
     vector.sort_unstable_by(|a, b| {
-        let a_is_dir = a.file_type().map_or(false, |ft| ft.is_dir());
-        let b_is_dir = b.file_type().map_or(false, |ft| ft.is_dir());
+        let a_is_dir = a.file_type().is_ok_and(|ft| ft.is_dir());
+        let b_is_dir = b.file_type().is_ok_and(|ft| ft.is_dir());
 
         if a_is_dir == b_is_dir {
             a.file_name().cmp(&b.file_name())
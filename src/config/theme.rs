@@ -0,0 +1,285 @@
+use super::file::existing_config_file;
+
+use serde::Deserialize;
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+static THEME_FILE: &str = "theme.toml";
+
+static ENTRIES: &[&str] = &["directory", "symlink", "target", "orphan", "special"];
+
+static STATUSES: &[&str] = &[
+    "ok",
+    "modified",
+    "missing",
+    "extra",
+    "staged",
+    "untracked",
+    "ignored",
+    "conflicted",
+];
+
+static COLUMNS: &[&str] = &[
+    "inode",
+    "device",
+    "permission",
+    "links",
+    "user",
+    "group",
+    "btime",
+    "mtime",
+    "atime",
+    "ctime",
+    "size",
+    "hash",
+    "type",
+    "lines",
+    "git_log",
+    "xattr",
+    "context",
+];
+
+/// User palette and branch glyphs, read from `theme.toml` in the
+/// configuration directory:
+///
+/// ```toml
+/// [entry]
+/// directory = "bold blue"
+/// symlink = "#ffaf00"
+///
+/// [status]
+/// modified = "black on_yellow"
+///
+/// [category]
+/// archive = "38;5;208"
+///
+/// [column]
+/// size = "bright_green"
+///
+/// [branch]
+/// end = "`-- "
+/// middle = "|-- "
+/// space = "    "
+/// structural = "|   "
+/// ```
+///
+/// Colours are either raw SGR parameters or space-separated words, see
+/// [`to_sgr`]. Anything left out keeps the built-in palette.
+#[derive(Debug, Default, Clone)]
+pub struct Theme {
+    entries: HashMap<String, String>,
+    statuses: HashMap<String, String>,
+    categories: HashMap<String, String>,
+    columns: HashMap<String, String>,
+    branch: Option<BranchGlyphs>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BranchGlyphs {
+    pub end: String,
+    pub middle: String,
+    pub space: String,
+    pub structural: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    entry: HashMap<String, String>,
+    status: HashMap<String, String>,
+    category: HashMap<String, String>,
+    column: HashMap<String, String>,
+    branch: Option<BranchGlyphs>,
+}
+
+impl Theme {
+    /// The user's `theme.toml`, if any.
+    pub fn from_user_config() -> anyhow::Result<Self> {
+        match existing_config_file(THEME_FILE) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Cannot read theme from '{}'", path.display()))?;
+
+        Self::parse(&content).with_context(|| format!("Invalid theme in '{}'", path.display()))
+    }
+
+    fn parse(content: &str) -> anyhow::Result<Self> {
+        let file: ThemeFile = toml::from_str(content)?;
+
+        Ok(Theme {
+            entries: resolve("entry", file.entry, Some(ENTRIES))?,
+            statuses: resolve("status", file.status, Some(STATUSES))?,
+            // Categories can be user-defined, so any name goes.
+            categories: resolve("category", file.category, None)?,
+            columns: resolve("column", file.column, Some(COLUMNS))?,
+            branch: file.branch,
+        })
+    }
+
+    pub fn entry(&self, role: &str) -> Option<&str> {
+        self.entries.get(role).map(String::as_str)
+    }
+
+    pub fn status(&self, status: &str) -> Option<&str> {
+        self.statuses.get(status).map(String::as_str)
+    }
+
+    pub fn category(&self, name: &str) -> Option<&str> {
+        self.categories.get(name).map(String::as_str)
+    }
+
+    pub fn column(&self, column: &str) -> Option<&str> {
+        self.columns.get(column).map(String::as_str)
+    }
+
+    pub fn branch(&self) -> Option<&BranchGlyphs> {
+        self.branch.as_ref()
+    }
+}
+
+/// Converts every colour of a table to SGR parameters, rejecting unknown keys.
+fn resolve(
+    table: &str,
+    colors: HashMap<String, String>,
+    known: Option<&[&str]>,
+) -> anyhow::Result<HashMap<String, String>> {
+    colors
+        .into_iter()
+        .map(|(key, color)| {
+            if known.is_some_and(|known| !known.contains(&key.as_str())) {
+                anyhow::bail!("Unknown key '{key}' in [{table}]");
+            }
+            let sgr = to_sgr(&color).ok_or_else(|| {
+                anyhow::anyhow!("Invalid colour '{color}' for '{key}' in [{table}]")
+            })?;
+            Ok((key, sgr))
+        })
+        .collect()
+}
+
+/// Converts a colour to SGR parameters. Accepts raw parameters such as
+/// `01;34` or `38;5;208`, or words such as `bold underline cyan`,
+/// `bright_red`, `on_blue` and `#rrggbb`.
+pub fn to_sgr(color: &str) -> Option<String> {
+    let color = color.trim();
+
+    if !color.is_empty() && color.bytes().all(|b| b.is_ascii_digit() || b == b';') {
+        return Some(color.to_string());
+    }
+
+    let codes = color
+        .split_whitespace()
+        .map(word_to_sgr)
+        .collect::<Option<Vec<String>>>()?;
+
+    (!codes.is_empty()).then(|| codes.join(";"))
+}
+
+fn word_to_sgr(word: &str) -> Option<String> {
+    let code = match word {
+        "bold" => 1,
+        "dim" => 2,
+        "italic" => 3,
+        "underline" => 4,
+        "blink" => 5,
+        "reverse" => 7,
+        _ => {
+            let (background, name) = match word.strip_prefix("on_") {
+                Some(name) => (true, name),
+                None => (false, word),
+            };
+
+            if let Some(hex) = name.strip_prefix('#') {
+                let rgb = u32::from_str_radix(hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 6)?;
+                let layer = if background { 48 } else { 38 };
+                return Some(format!(
+                    "{layer};2;{};{};{}",
+                    rgb >> 16,
+                    (rgb >> 8) & 0xff,
+                    rgb & 0xff
+                ));
+            }
+
+            let (bright, name) = match name.strip_prefix("bright_") {
+                Some(name) => (true, name),
+                None => (false, name),
+            };
+            let base = match name {
+                "black" => 0,
+                "red" => 1,
+                "green" => 2,
+                "yellow" => 3,
+                "blue" => 4,
+                "magenta" | "purple" => 5,
+                "cyan" => 6,
+                "white" => 7,
+                _ => return None,
+            };
+
+            match (background, bright) {
+                (false, false) => 30 + base,
+                (false, true) => 90 + base,
+                (true, false) => 40 + base,
+                (true, true) => 100 + base,
+            }
+        }
+    };
+
+    Some(code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_sgr() {
+        assert_eq!(to_sgr("01;34").as_deref(), Some("01;34"));
+        assert_eq!(to_sgr("bold blue").as_deref(), Some("1;34"));
+        assert_eq!(to_sgr("black on_bright_yellow").as_deref(), Some("30;103"));
+        assert_eq!(to_sgr("#ff8000").as_deref(), Some("38;2;255;128;0"));
+        assert_eq!(to_sgr("on_#000010").as_deref(), Some("48;2;0;0;16"));
+        assert_eq!(to_sgr("blurple"), None);
+        assert_eq!(to_sgr(""), None);
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let theme = Theme::parse(
+            r##"
+            [entry]
+            directory = "bold blue"
+
+            [column]
+            size = "33"
+
+            [branch]
+            end = "`-- "
+            middle = "|-- "
+            space = "    "
+            structural = "|   "
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(theme.entry("directory"), Some("1;34"));
+        assert_eq!(theme.entry("symlink"), None);
+        assert_eq!(theme.column("size"), Some("33"));
+        assert_eq!(theme.branch().unwrap().middle, "|-- ");
+
+        assert!(Theme::parse("[entry]\nfolder = \"blue\"").is_err());
+        assert!(Theme::parse("[column]\nsize = \"blurple\"").is_err());
+    }
+}
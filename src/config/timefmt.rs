@@ -0,0 +1,257 @@
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::SecondsFormat;
+use chrono::TimeZone;
+use chrono::Utc;
+
+use unicode_width::UnicodeWidthStr;
+
+use std::fmt::Display;
+use std::time::Duration;
+use std::time::SystemTime;
+
+static DEFAULT_PATTERN: &str = "%d-%m-%Y %H:%M";
+static DEFAULT_NANOS_PATTERN: &str = "%d-%m-%Y %H:%M:%S%.9f";
+static ISO_PATTERN: &str = "%Y-%m-%dT%H:%M:%S%:z";
+static ISO_NANOS_PATTERN: &str = "%Y-%m-%dT%H:%M:%S%.9f%:z";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeStyle {
+    /// `19-06-2024 18:37`
+    Default,
+    /// `2024-06-19T18:37:05+08:00`
+    Iso,
+    /// `2024-06-19T18:37:05+08:00`, or with a `Z` suffix in UTC
+    Rfc3339,
+    /// `3h ago`
+    Relative,
+    /// User supplied strftime pattern
+    Custom,
+}
+
+/// Decides how timestamps in the `btime`, `mtime`, `atime` and `ctime` columns are printed.
+#[derive(Debug, Clone)]
+pub struct TimeFormat {
+    style: TimeStyle,
+    pattern: String,
+    utc: bool,
+    nanos: bool,
+    /// Reference point for relative timestamps.
+    now: SystemTime,
+    /// Display width reserved for a timestamp, measured whenever the format changes.
+    width: usize,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        let mut fmt = TimeFormat {
+            style: TimeStyle::Default,
+            pattern: DEFAULT_PATTERN.to_string(),
+            utc: false,
+            nanos: false,
+            now: SystemTime::now(),
+            width: 0,
+        };
+        fmt.measure();
+        fmt
+    }
+}
+
+impl TimeFormat {
+    pub fn with_style(&mut self, style: TimeStyle) {
+        self.style = style;
+        self.measure();
+    }
+
+    /// Use a strftime `pattern`, rejecting specifiers chrono does not understand.
+    pub fn with_pattern(&mut self, pattern: &str) -> anyhow::Result<()> {
+        if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+            return Err(anyhow::anyhow!("Invalid time format '{pattern}'"));
+        }
+
+        self.style = TimeStyle::Custom;
+        self.pattern = pattern.to_string();
+        self.measure();
+        Ok(())
+    }
+
+    pub fn with_utc(&mut self) {
+        self.utc = true;
+        self.measure();
+    }
+
+    /// Print seconds with nanosecond precision, which only the preset absolute styles have.
+    pub fn with_nanos(&mut self) -> anyhow::Result<()> {
+        if matches!(self.style, TimeStyle::Relative | TimeStyle::Custom) {
+            return Err(anyhow::anyhow!(
+                "--nanos cannot be combined with relative times or --timefmt, use %f in the pattern"
+            ));
+        }
+
+        self.nanos = true;
+        self.measure();
+        Ok(())
+    }
+
+    pub fn format(&self, time: SystemTime) -> String {
+        if self.style == TimeStyle::Relative {
+            return format_relative(time, self.now);
+        }
+
+        if self.utc {
+            self.format_in(DateTime::<Utc>::from(time))
+        } else {
+            self.format_in(DateTime::<Local>::from(time))
+        }
+    }
+
+    /// How far `time` is from the moment the run started, e.g. `3h ago`.
+    pub fn relative(&self, time: SystemTime) -> String {
+        format_relative(time, self.now)
+    }
+
+    /// Display width reserved for a formatted timestamp, so the columns line up.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn measure(&mut self) {
+        if self.style == TimeStyle::Relative {
+            self.width = 8;
+            return;
+        }
+
+        // Noon on each day of a week in September, the longest English month
+        // name, stays in September and covers every weekday in any time zone.
+        let noon = SystemTime::UNIX_EPOCH + Duration::new(968_846_400, 999_999_999);
+        self.width = (0..7)
+            .map(|day| self.format(noon + Duration::from_secs(day * 86_400)))
+            .map(|text| UnicodeWidthStr::width(text.as_str()))
+            .max()
+            .unwrap_or(0);
+    }
+
+    fn format_in<Tz>(&self, datetime: DateTime<Tz>) -> String
+    where
+        Tz: TimeZone,
+        Tz::Offset: Display,
+    {
+        let pattern = match (self.style, self.nanos) {
+            (TimeStyle::Rfc3339, nanos) => {
+                let precision = if nanos {
+                    SecondsFormat::Nanos
+                } else {
+                    SecondsFormat::Secs
+                };
+                return datetime.to_rfc3339_opts(precision, self.utc);
+            }
+            (TimeStyle::Iso, false) => ISO_PATTERN,
+            (TimeStyle::Iso, true) => ISO_NANOS_PATTERN,
+            (TimeStyle::Default, true) => DEFAULT_NANOS_PATTERN,
+            _ => &self.pattern,
+        };
+
+        datetime.format(pattern).to_string()
+    }
+}
+
+/// Describes how far `time` is from `now`, e.g. `3h ago` or `in 2d`.
+pub fn format_relative(time: SystemTime, now: SystemTime) -> String {
+    let (secs, future) = match now.duration_since(time) {
+        Ok(elapsed) => (elapsed.as_secs(), false),
+        Err(err) => (err.duration().as_secs(), true),
+    };
+
+    if secs < 60 {
+        return "just now".to_string();
+    }
+
+    let amount = match secs {
+        s if s < 3_600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3_600),
+        s if s < 30 * 86_400 => format!("{}d", s / 86_400),
+        s if s < 365 * 86_400 => format!("{}mo", s / (30 * 86_400)),
+        s => format!("{}y", s / (365 * 86_400)),
+    };
+
+    if future {
+        format!("in {amount}")
+    } else {
+        format!("{amount} ago")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::UNIX_EPOCH;
+
+    // 2024-06-19T18:37:05.123456789Z
+    fn sample() -> SystemTime {
+        UNIX_EPOCH + Duration::new(1_718_822_225, 123_456_789)
+    }
+
+    fn utc_format() -> TimeFormat {
+        let mut fmt = TimeFormat::default();
+        fmt.with_utc();
+        fmt
+    }
+
+    #[test]
+    fn test_presets() {
+        let mut fmt = utc_format();
+        assert_eq!(fmt.format(sample()), "19-06-2024 18:37");
+
+        fmt.with_style(TimeStyle::Iso);
+        assert_eq!(fmt.format(sample()), "2024-06-19T18:37:05+00:00");
+
+        fmt.with_style(TimeStyle::Rfc3339);
+        assert_eq!(fmt.format(sample()), "2024-06-19T18:37:05Z");
+
+        fmt.with_nanos().unwrap();
+        assert_eq!(fmt.format(sample()), "2024-06-19T18:37:05.123456789Z");
+    }
+
+    #[test]
+    fn test_custom_pattern() {
+        let mut fmt = utc_format();
+        fmt.with_pattern("%Y/%m/%d").unwrap();
+        assert_eq!(fmt.format(sample()), "2024/06/19");
+
+        assert!(fmt.with_pattern("%Q").is_err());
+        assert!(fmt.with_nanos().is_err());
+    }
+
+    #[test]
+    fn test_width() {
+        let mut fmt = utc_format();
+        assert_eq!(fmt.width(), "19-06-2024 18:37".len());
+
+        fmt.with_pattern("%A %B").unwrap();
+        assert_eq!(fmt.width(), "Wednesday September".len());
+
+        fmt.with_style(TimeStyle::Relative);
+        assert_eq!(fmt.width(), 8);
+    }
+
+    #[test]
+    fn test_relative() {
+        let now = sample();
+        assert_eq!(format_relative(now, now), "just now");
+        assert_eq!(
+            format_relative(now - Duration::from_secs(3 * 3_600), now),
+            "3h ago"
+        );
+        assert_eq!(
+            format_relative(now - Duration::from_secs(400 * 86_400), now),
+            "1y ago"
+        );
+        assert_eq!(
+            format_relative(now + Duration::from_secs(2 * 86_400), now),
+            "in 2d"
+        );
+    }
+}
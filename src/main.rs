@@ -19,6 +19,7 @@ fn main() -> anyhow::Result<()> {
     tr.print_head()?;
     tr.walk_dir(tr.path_builder.base_path())?;
     tr.print_report(report_mode)?;
+    tr.digest.finish()?;
 
     Ok(())
 }
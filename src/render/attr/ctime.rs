@@ -0,0 +1,76 @@
+use crate::config::timefmt::TimeFormat;
+use crate::render::buffer::Buffer;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+
+pub type FnExtChangeTime<W> = fn(&mut Buffer<W>, &Metadata, &TimeFormat) -> io::Result<()>;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's status-change-time
+    pub fn print_ctime(
+        &mut self,
+        meta: &Metadata,
+        fmt: &TimeFormat,
+        f: FnExtChangeTime<W>,
+    ) -> io::Result<()> {
+        f(self, meta, fmt)
+    }
+
+    pub fn write_ctime(&mut self, meta: &Metadata, fmt: &TimeFormat) -> io::Result<()> {
+        self.write_timestamp(change_time(meta), fmt)
+    }
+
+    pub fn write_no_ctime(&mut self, _meta: &Metadata, _fmt: &TimeFormat) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Inode change time, which `std` does not expose as a `SystemTime`.
+#[cfg(unix)]
+fn change_time(meta: &Metadata) -> io::Result<std::time::SystemTime> {
+    use std::os::unix::fs::MetadataExt;
+
+    Ok(from_unix(meta.ctime(), meta.ctime_nsec() as u32))
+}
+
+/// `secs` seconds from the epoch, possibly before it, plus `nanos` towards the future.
+#[cfg(unix)]
+fn from_unix(secs: i64, nanos: u32) -> std::time::SystemTime {
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    let whole = Duration::from_secs(secs.unsigned_abs());
+    let base = if secs >= 0 {
+        UNIX_EPOCH + whole
+    } else {
+        UNIX_EPOCH - whole
+    };
+    base + Duration::from_nanos(u64::from(nanos))
+}
+
+#[cfg(not(unix))]
+fn change_time(_meta: &Metadata) -> io::Result<std::time::SystemTime> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "ctime is not available on this platform",
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_from_unix() {
+        assert_eq!(from_unix(2, 500), UNIX_EPOCH + Duration::new(2, 500));
+        // -2s + 0.25s is 1.75s before the epoch
+        assert_eq!(
+            from_unix(-2, 250_000_000),
+            UNIX_EPOCH - Duration::new(1, 750_000_000)
+        );
+    }
+}
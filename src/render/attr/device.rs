@@ -0,0 +1,32 @@
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+
+pub type FnExtDevice<W> = fn(&mut Buffer<W>, &Metadata) -> io::Result<()>;
+
+const DEVICE_WIDTH: usize = 6;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's device ID
+    pub fn print_device(&mut self, meta: &Metadata, f: FnExtDevice<W>) -> io::Result<()> {
+        f(self, meta)
+    }
+
+    #[cfg(unix)]
+    pub fn write_device(&mut self, meta: &Metadata) -> io::Result<()> {
+        use std::os::unix::fs::MetadataExt;
+
+        self.write_cell(&meta.dev().to_string(), DEVICE_WIDTH, Align::Right)
+    }
+
+    #[cfg(not(unix))]
+    pub fn write_device(&mut self, _meta: &Metadata) -> io::Result<()> {
+        self.write_cell("─", DEVICE_WIDTH, Align::Right)
+    }
+
+    pub fn write_no_device(&mut self, _meta: &Metadata) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use crate::walk::magic::FileKind;
+use std::io;
+use std::io::Write;
+
+pub type FnExtFileType<W> = fn(&mut Buffer<W>, Option<FileKind>) -> io::Result<()>;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's content-detected type
+    pub fn print_file_type(
+        &mut self,
+        kind: Option<FileKind>,
+        f: FnExtFileType<W>,
+    ) -> io::Result<()> {
+        f(self, kind)
+    }
+
+    pub fn write_file_type(&mut self, kind: Option<FileKind>) -> io::Result<()> {
+        let label = kind.map(|kind| kind.label()).unwrap_or("─");
+        self.write_cell(label, FileKind::LABEL_WIDTH, Align::Left)
+    }
+
+    pub fn write_no_file_type(&mut self, _kind: Option<FileKind>) -> io::Result<()> {
+        Ok(())
+    }
+}
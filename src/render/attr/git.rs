@@ -0,0 +1,24 @@
+use crate::render::buffer::Buffer;
+use crate::walk::git::GitStatus;
+use std::io;
+use std::io::Write;
+
+pub type FnExtGit<W> = fn(&mut Buffer<W>, Option<GitStatus>) -> io::Result<()>;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's git status
+    pub fn print_git(&mut self, status: Option<GitStatus>, f: FnExtGit<W>) -> io::Result<()> {
+        f(self, status)
+    }
+
+    pub fn write_git(&mut self, status: Option<GitStatus>) -> io::Result<()> {
+        let marker = status.unwrap_or(GitStatus::Clean).marker();
+        self.write_space()?;
+        self.bufwr.write_all(marker.as_bytes())?;
+        self.write_space()
+    }
+
+    pub fn write_no_git(&mut self, _status: Option<GitStatus>) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use crate::config::timefmt::TimeFormat;
+use crate::render::buffer::Buffer;
+use crate::render::layout;
+use crate::render::layout::Align;
+use crate::walk::git::LastCommit;
+use std::io;
+use std::io::Write;
+
+pub type FnExtGitLog<W> = fn(&mut Buffer<W>, Option<&LastCommit>, &TimeFormat) -> io::Result<()>;
+
+const AUTHOR_WIDTH: usize = 16;
+
+impl<W: Write> Buffer<W> {
+    /// Print the last commit that touched the entry
+    pub fn print_git_log(
+        &mut self,
+        commit: Option<&LastCommit>,
+        fmt: &TimeFormat,
+        f: FnExtGitLog<W>,
+    ) -> io::Result<()> {
+        f(self, commit, fmt)
+    }
+
+    pub fn write_git_log(
+        &mut self,
+        commit: Option<&LastCommit>,
+        fmt: &TimeFormat,
+    ) -> io::Result<()> {
+        let (hash, author, date) = match commit {
+            Some(commit) => (
+                commit.hash.as_str(),
+                layout::truncate(&commit.author, AUTHOR_WIDTH),
+                fmt.relative(commit.time),
+            ),
+            None => ("─", "─".to_string(), "─".to_string()),
+        };
+
+        let author = layout::pad(&author, AUTHOR_WIDTH, Align::Left);
+        let date = layout::pad(&date, 8, Align::Right);
+        self.write_cell(&format!("{hash:<9} {author} {date}"), 0, Align::Left)
+    }
+
+    pub fn write_no_git_log(
+        &mut self,
+        _commit: Option<&LastCommit>,
+        _fmt: &TimeFormat,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use crate::render::buffer::Buffer;
+use std::io;
+use std::io::Write;
+
+pub type FnExtHash<W> = fn(&mut Buffer<W>, &str) -> io::Result<()>;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's content digest
+    pub fn print_hash(&mut self, sum: &str, f: FnExtHash<W>) -> io::Result<()> {
+        f(self, sum)
+    }
+
+    pub fn write_hash(&mut self, sum: &str) -> io::Result<()> {
+        self.write_space()?;
+        self.bufwr.write_all(sum.as_bytes())?;
+        self.write_space()
+    }
+
+    pub fn write_no_hash(&mut self, _sum: &str) -> io::Result<()> {
+        Ok(())
+    }
+}
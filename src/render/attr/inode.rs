@@ -0,0 +1,32 @@
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+
+pub type FnExtInode<W> = fn(&mut Buffer<W>, &Metadata) -> io::Result<()>;
+
+const INODE_WIDTH: usize = 10;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's inode number
+    pub fn print_inode(&mut self, meta: &Metadata, f: FnExtInode<W>) -> io::Result<()> {
+        f(self, meta)
+    }
+
+    #[cfg(unix)]
+    pub fn write_inode(&mut self, meta: &Metadata) -> io::Result<()> {
+        use std::os::unix::fs::MetadataExt;
+
+        self.write_cell(&meta.ino().to_string(), INODE_WIDTH, Align::Right)
+    }
+
+    #[cfg(not(unix))]
+    pub fn write_inode(&mut self, _meta: &Metadata) -> io::Result<()> {
+        self.write_cell("─", INODE_WIDTH, Align::Right)
+    }
+
+    pub fn write_no_inode(&mut self, _meta: &Metadata) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use std::io;
+use std::io::Write;
+
+pub type FnExtLines<W> = fn(&mut Buffer<W>, Option<u64>) -> io::Result<()>;
+
+const LINES_WIDTH: usize = 8;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's line count, rolled up for directories
+    pub fn print_lines(&mut self, lines: Option<u64>, f: FnExtLines<W>) -> io::Result<()> {
+        f(self, lines)
+    }
+
+    pub fn write_lines(&mut self, lines: Option<u64>) -> io::Result<()> {
+        let lines = lines.map(|n| n.to_string());
+        self.write_cell(lines.as_deref().unwrap_or("─"), LINES_WIDTH, Align::Right)
+    }
+
+    pub fn write_no_lines(&mut self, _lines: Option<u64>) -> io::Result<()> {
+        Ok(())
+    }
+}
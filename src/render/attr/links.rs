@@ -0,0 +1,32 @@
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+
+pub type FnExtLinks<W> = fn(&mut Buffer<W>, &Metadata) -> io::Result<()>;
+
+const LINKS_WIDTH: usize = 3;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's hard-link count
+    pub fn print_links(&mut self, meta: &Metadata, f: FnExtLinks<W>) -> io::Result<()> {
+        f(self, meta)
+    }
+
+    #[cfg(unix)]
+    pub fn write_links(&mut self, meta: &Metadata) -> io::Result<()> {
+        use std::os::unix::fs::MetadataExt;
+
+        self.write_cell(&meta.nlink().to_string(), LINKS_WIDTH, Align::Right)
+    }
+
+    #[cfg(not(unix))]
+    pub fn write_links(&mut self, _meta: &Metadata) -> io::Result<()> {
+        self.write_cell("─", LINKS_WIDTH, Align::Right)
+    }
+
+    pub fn write_no_links(&mut self, _meta: &Metadata) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,5 +1,18 @@
 pub mod atime;
 pub mod btime;
+pub mod ctime;
+pub mod device;
+pub mod ftype;
+pub mod git;
+pub mod gitlog;
+pub mod group;
+pub mod hash;
+pub mod inode;
+pub mod lines;
+pub mod links;
 pub mod mtime;
 pub mod pms;
 pub mod size;
+pub mod timestamp;
+pub mod user;
+pub mod xattr;
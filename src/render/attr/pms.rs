@@ -3,92 +3,73 @@ use std::fs::Metadata;
 use std::io;
 use std::io::Write;
 
-pub type FnExtPermission<W> = fn(&mut Buffer<W>, &Metadata) -> io::Result<()>;
+/// The flag tells whether the entry has a POSIX ACL, if `--acl` was requested.
+pub type FnExtPermission<W> = fn(&mut Buffer<W>, &Metadata, Option<bool>) -> io::Result<()>;
 
 impl<W: Write> Buffer<W> {
     /// Print entry's permission
-    pub fn print_permission(&mut self, meta: &Metadata, f: FnExtPermission<W>) -> io::Result<()> {
-        f(self, meta)
+    pub fn print_permission(
+        &mut self,
+        meta: &Metadata,
+        acl: Option<bool>,
+        f: FnExtPermission<W>,
+    ) -> io::Result<()> {
+        f(self, meta, acl)
     }
 
-    pub fn write_no_permission(&mut self, _meta: &Metadata) -> io::Result<()> {
+    pub fn write_no_permission(&mut self, _meta: &Metadata, _acl: Option<bool>) -> io::Result<()> {
         Ok(())
     }
 
     #[cfg(unix)]
-    pub fn write_permission(&mut self, meta: &Metadata) -> io::Result<()> {
+    pub fn write_permission(&mut self, meta: &Metadata, acl: Option<bool>) -> io::Result<()> {
         use std::os::unix::fs::PermissionsExt;
 
         let mode = meta.permissions().mode();
 
         self.write_space()?;
+        self.bufwr.write_all(&mode_to_symbolic(mode))?;
+        self.write_acl_marker(acl)?;
+        self.write_space()
+    }
 
-        if meta.is_dir() {
-            self.bufwr.write_all("d".as_bytes())?;
-        } else {
-            self.bufwr.write_all(".".as_bytes())?;
-        }
-
-        if mode & 0o400 != 0 {
-            self.bufwr.write_all("r".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
-
-        if mode & 0o200 != 0 {
-            self.bufwr.write_all("w".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
-
-        if mode & 0o100 != 0 {
-            self.bufwr.write_all("x".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
-
-        if mode & 0o40 != 0 {
-            self.bufwr.write_all("r".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
-
-        if mode & 0o20 != 0 {
-            self.bufwr.write_all("w".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
+    /// Print entry's permission as octal digits, e.g. `1777`.
+    #[cfg(unix)]
+    pub fn write_octal_permission(&mut self, meta: &Metadata, acl: Option<bool>) -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
 
-        if mode & 0o10 != 0 {
-            self.bufwr.write_all("x".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
+        let mode = meta.permissions().mode();
 
-        if mode & 0o4 != 0 {
-            self.bufwr.write_all("r".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
+        self.write_space()?;
+        self.bufwr
+            .write_all(format!("{:04o}", mode & 0o7777).as_bytes())?;
+        self.write_acl_marker(acl)?;
+        self.write_space()
+    }
 
-        if mode & 0o2 != 0 {
-            self.bufwr.write_all("w".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
-        }
+    #[cfg(windows)]
+    pub fn write_octal_permission(
+        &mut self,
+        _meta: &Metadata,
+        _acl: Option<bool>,
+    ) -> io::Result<()> {
+        self.write_space()?;
+        self.bufwr.write_all("────".as_bytes())?;
+        self.write_space()
+    }
 
-        if mode & 0o1 != 0 {
-            self.bufwr.write_all("x".as_bytes())?;
-        } else {
-            self.bufwr.write_all("-".as_bytes())?;
+    /// Mark entries carrying a POSIX ACL with `+`, like `ls -l`.
+    fn write_acl_marker(&mut self, acl: Option<bool>) -> io::Result<()> {
+        match acl {
+            Some(true) => self.bufwr.write_all(b"+"),
+            Some(false) => self.bufwr.write_all(b" "),
+            None => Ok(()),
         }
-
-        self.write_space()
     }
 
     // TODO:
     #[cfg(windows)]
-    pub fn write_permission(&mut self, meta: &Metadata) -> io::Result<()> {
+    pub fn write_permission(&mut self, meta: &Metadata, acl: Option<bool>) -> io::Result<()> {
         use std::os::windows::fs::MetadataExt;
         use winapi::um::winnt::{GENERIC_EXECUTE, GENERIC_READ, GENERIC_WRITE};
 
@@ -166,3 +147,86 @@ impl<W: Write> Buffer<W> {
         self.write_space()
     }
 }
+
+#[cfg(unix)]
+const S_IFMT: u32 = 0o170000;
+#[cfg(unix)]
+const S_IFSOCK: u32 = 0o140000;
+#[cfg(unix)]
+const S_IFLNK: u32 = 0o120000;
+#[cfg(unix)]
+const S_IFBLK: u32 = 0o060000;
+#[cfg(unix)]
+const S_IFDIR: u32 = 0o040000;
+#[cfg(unix)]
+const S_IFCHR: u32 = 0o020000;
+#[cfg(unix)]
+const S_IFIFO: u32 = 0o010000;
+
+/// Render `mode` the way `ls -l` does, e.g. `drwxrwxrwt` or `lrwxrwxrwx`,
+/// except regular files use `.` for the file type.
+#[cfg(unix)]
+fn mode_to_symbolic(mode: u32) -> [u8; 10] {
+    let file_type = match mode & S_IFMT {
+        S_IFDIR => b'd',
+        S_IFLNK => b'l',
+        S_IFIFO => b'p',
+        S_IFSOCK => b's',
+        S_IFBLK => b'b',
+        S_IFCHR => b'c',
+        _ => b'.',
+    };
+
+    // Execute bit combined with setuid, setgid or sticky bit.
+    let special = |exec: u32, special: u32, set: u8| match (mode & exec != 0, mode & special != 0) {
+        (true, true) => set,
+        (false, true) => set.to_ascii_uppercase(),
+        (true, false) => b'x',
+        (false, false) => b'-',
+    };
+
+    let bit = |mask: u32, c: u8| if mode & mask != 0 { c } else { b'-' };
+
+    [
+        file_type,
+        bit(0o400, b'r'),
+        bit(0o200, b'w'),
+        special(0o100, 0o4000, b's'),
+        bit(0o40, b'r'),
+        bit(0o20, b'w'),
+        special(0o10, 0o2000, b's'),
+        bit(0o4, b'r'),
+        bit(0o2, b'w'),
+        special(0o1, 0o1000, b't'),
+    ]
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn symbolic(mode: u32) -> String {
+        String::from_utf8(mode_to_symbolic(mode).to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_file_types() {
+        assert_eq!(symbolic(0o100644), ".rw-r--r--");
+        assert_eq!(symbolic(0o040755), "drwxr-xr-x");
+        assert_eq!(symbolic(0o120777), "lrwxrwxrwx");
+        assert_eq!(symbolic(0o010644), "prw-r--r--");
+        assert_eq!(symbolic(0o140755), "srwxr-xr-x");
+        assert_eq!(symbolic(0o060660), "brw-rw----");
+        assert_eq!(symbolic(0o020620), "crw--w----");
+    }
+
+    #[test]
+    fn test_special_bits() {
+        assert_eq!(symbolic(0o041777), "drwxrwxrwt");
+        assert_eq!(symbolic(0o041776), "drwxrwxrwT");
+        assert_eq!(symbolic(0o104755), ".rwsr-xr-x");
+        assert_eq!(symbolic(0o104644), ".rwSr--r--");
+        assert_eq!(symbolic(0o102755), ".rwxr-sr-x");
+        assert_eq!(symbolic(0o102745), ".rwxr-Sr-x");
+    }
+}
@@ -1,10 +1,15 @@
 use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use crate::walk::visit::allocated_size;
 use std::fs::Metadata;
 use std::io;
 use std::io::Write;
 
 pub type FnExtSize<W> = fn(&mut Buffer<W>, &Metadata) -> io::Result<()>;
 
+/// Right-aligned bytes, wide enough for files up to 999 TB.
+const SIZE_WIDTH: usize = 15;
+
 impl<W: Write> Buffer<W> {
     /// Print entry's size
     pub fn print_size(&mut self, meta: &Metadata, f: FnExtSize<W>) -> io::Result<()> {
@@ -16,13 +21,15 @@ impl<W: Write> Buffer<W> {
     }
 
     pub fn write_size(&mut self, meta: &Metadata) -> io::Result<()> {
-        let size = meta.len();
-        // Only 931.32 gigabytes, or 999999999999 bytes, can be supported at most by the padding.
-        // If the size is exceeded, the tree output will be distorted and not symmetrical.
-        let padded_string = format!("{:^12}", size.to_string());
+        self.write_padded_size(meta.len())
+    }
 
-        self.bufwr.write_all(padded_string.as_bytes())?;
+    /// Print allocated blocks (`st_blocks * 512`) instead of the apparent length.
+    pub fn write_disk_usage(&mut self, meta: &Metadata) -> io::Result<()> {
+        self.write_padded_size(allocated_size(meta))
+    }
 
-        Ok(())
+    fn write_padded_size(&mut self, size: u64) -> io::Result<()> {
+        self.write_cell(&size.to_string(), SIZE_WIDTH, Align::Right)
     }
 }
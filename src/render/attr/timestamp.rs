@@ -0,0 +1,23 @@
+use crate::config::timefmt::TimeFormat;
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use std::io;
+use std::io::Write;
+use std::time::SystemTime;
+
+impl<W: Write> Buffer<W> {
+    /// Shared by the time columns. Prints a placeholder when the platform
+    /// or filesystem cannot provide the requested timestamp.
+    pub fn write_timestamp(
+        &mut self,
+        time: io::Result<SystemTime>,
+        fmt: &TimeFormat,
+    ) -> io::Result<()> {
+        let text = match time {
+            Ok(time) => fmt.format(time),
+            Err(_) => "─".to_string(),
+        };
+
+        self.write_cell(&text, fmt.width(), Align::Left)
+    }
+}
@@ -0,0 +1,41 @@
+use crate::config::accounts::Accounts;
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+
+pub type FnExtUser<W> = fn(&mut Buffer<W>, &Metadata, &mut Accounts) -> io::Result<()>;
+
+/// Fits common account names. Longer ones are printed whole and push the
+/// rest of their row along rather than lose characters.
+const NAME_WIDTH: usize = 8;
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's owner
+    pub fn print_user(
+        &mut self,
+        meta: &Metadata,
+        accounts: &mut Accounts,
+        f: FnExtUser<W>,
+    ) -> io::Result<()> {
+        f(self, meta, accounts)
+    }
+
+    #[cfg(unix)]
+    pub fn write_user(&mut self, meta: &Metadata, accounts: &mut Accounts) -> io::Result<()> {
+        use std::os::unix::fs::MetadataExt;
+
+        let name = accounts.user_name(meta.uid());
+        self.write_cell(&name, NAME_WIDTH, Align::Left)
+    }
+
+    #[cfg(not(unix))]
+    pub fn write_user(&mut self, _meta: &Metadata, _accounts: &mut Accounts) -> io::Result<()> {
+        self.write_cell("─", NAME_WIDTH, Align::Left)
+    }
+
+    pub fn write_no_user(&mut self, _meta: &Metadata, _accounts: &mut Accounts) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::render::buffer::Buffer;
+use crate::render::layout::Align;
+use crate::walk::xattr;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+pub type FnExtXattr<W> = fn(&mut Buffer<W>, &Path) -> io::Result<()>;
+pub type FnExtContext<W> = fn(&mut Buffer<W>, &Path) -> io::Result<()>;
+
+/// Minimum widths. Attribute lists and labels are never cut, these are the
+/// last columns before the tree so a long value only shifts its own row.
+const XATTR_WIDTH: usize = 24;
+const CONTEXT_WIDTH: usize = 32;
+
+impl<W: Write> Buffer<W> {
+    /// Print the names of entry's extended attributes
+    pub fn print_xattr(&mut self, path: &Path, f: FnExtXattr<W>) -> io::Result<()> {
+        f(self, path)
+    }
+
+    pub fn write_xattr(&mut self, path: &Path) -> io::Result<()> {
+        let names = xattr::names(path);
+        let names = if names.is_empty() {
+            "─".to_string()
+        } else {
+            names.join(",")
+        };
+
+        self.write_cell(&names, XATTR_WIDTH, Align::Left)
+    }
+
+    pub fn write_no_xattr(&mut self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Print entry's SELinux security context
+    pub fn print_context(&mut self, path: &Path, f: FnExtContext<W>) -> io::Result<()> {
+        f(self, path)
+    }
+
+    pub fn write_context(&mut self, path: &Path) -> io::Result<()> {
+        let context = xattr::security_context(path).unwrap_or_else(|| "?".to_string());
+
+        self.write_cell(&context, CONTEXT_WIDTH, Align::Left)
+    }
+
+    pub fn write_no_context(&mut self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+}
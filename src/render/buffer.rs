@@ -1,3 +1,6 @@
+use crate::render::quote::QuotingStyle;
+
+use std::ffi::OsStr;
 use std::ffi::OsString;
 use std::io;
 use std::io::Write;
@@ -5,6 +8,9 @@ use std::io::Write;
 #[derive(Debug)]
 pub struct Buffer<W: Write> {
     pub bufwr: io::BufWriter<W>,
+    quoting: QuotingStyle,
+    /// Wrap entry names in OSC 8 file links.
+    hyperlinks: bool,
 }
 
 pub trait IntoBranch<W: Write> {
@@ -20,7 +26,25 @@ impl<W: Write> IntoBranch<W> for Buffer<W> {
 impl<W: Write> Buffer<W> {
     pub fn new(writer: W) -> anyhow::Result<Self> {
         let bufwr = io::BufWriter::new(writer);
-        Ok(Buffer { bufwr })
+        Ok(Buffer {
+            bufwr,
+            quoting: QuotingStyle::Literal,
+            hyperlinks: false,
+        })
+    }
+
+    /// Quote entry names written through `write_os_string` with `quoting`.
+    pub fn with_quoting(&mut self, quoting: QuotingStyle) {
+        self.quoting = quoting;
+    }
+
+    /// Link entry names written through the entree writers to their files.
+    pub fn with_hyperlinks(&mut self) {
+        self.hyperlinks = true;
+    }
+
+    pub fn has_hyperlinks(&self) -> bool {
+        self.hyperlinks
     }
 
     pub fn write_message(&mut self, message: &str) -> io::Result<()> {
@@ -28,7 +52,13 @@ impl<W: Write> Buffer<W> {
     }
 
     pub fn write_os_string(&mut self, message: OsString) -> io::Result<()> {
-        self.bufwr.write_all(message.as_encoded_bytes())
+        self.write_name(&message)
+    }
+
+    /// Write an entry name or path, quoted with the selected quoting style.
+    pub fn write_name(&mut self, name: &OsStr) -> io::Result<()> {
+        let quoted = self.quoting.quote(name.as_encoded_bytes());
+        self.bufwr.write_all(&quoted)
     }
 }
 
@@ -3,6 +3,8 @@ use std::io;
 use std::io::Write;
 
 pub type FnColor<W> = fn(&mut Buffer<W>) -> io::Result<()>;
+/// Paints with the SGR parameters of a user-defined style, e.g. `01;34`.
+pub type FnPaint<W> = fn(&mut Buffer<W>, &str) -> io::Result<()>;
 
 impl<W: Write> Buffer<W> {
     pub fn reset_color(&mut self) -> io::Result<()> {
@@ -33,7 +35,33 @@ impl<W: Write> Buffer<W> {
         self.bufwr.write_all(b"\x1B[1;35m")
     }
 
+    pub fn red(&mut self) -> io::Result<()> {
+        self.bufwr.write_all(b"\x1b[0;31m")
+    }
+
+    pub fn bold_green(&mut self) -> io::Result<()> {
+        self.bufwr.write_all(b"\x1b[1;32m")
+    }
+
+    pub fn cyan(&mut self) -> io::Result<()> {
+        self.bufwr.write_all(b"\x1b[0;36m")
+    }
+
+    pub fn orphan(&mut self) -> io::Result<()> {
+        self.bufwr.write_all(b"\x1b[1;31;40m")
+    }
+
     pub fn no_color(&mut self) -> io::Result<()> {
         Ok(())
     }
+
+    pub fn paint(&mut self, style: &str) -> io::Result<()> {
+        self.bufwr.write_all(b"\x1b[")?;
+        self.bufwr.write_all(style.as_bytes())?;
+        self.bufwr.write_all(b"m")
+    }
+
+    pub fn no_paint(&mut self, _style: &str) -> io::Result<()> {
+        Ok(())
+    }
 }
@@ -0,0 +1,90 @@
+use crate::render::buffer::Buffer;
+
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+
+pub type FnOutIndicator<W> = fn(&mut Buffer<W>, &Metadata) -> io::Result<()>;
+
+impl<W: Write> Buffer<W> {
+    /// Print the `--classify` indicator after an entry's name
+    pub fn print_indicator(&mut self, meta: &Metadata, f: FnOutIndicator<W>) -> io::Result<()> {
+        f(self, meta)
+    }
+
+    pub fn write_indicator(&mut self, meta: &Metadata) -> io::Result<()> {
+        if let Some(indicator) = indicator_of(meta) {
+            self.bufwr.write_all(&[indicator])?;
+        }
+        Ok(())
+    }
+
+    pub fn write_no_indicator(&mut self, _meta: &Metadata) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Type indicator appended by `ls -F` and `tree -F`, `None` for plain files.
+#[cfg(unix)]
+fn indicator_of(meta: &Metadata) -> Option<u8> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    let ftype = meta.file_type();
+    if ftype.is_dir() {
+        Some(b'/')
+    } else if ftype.is_symlink() {
+        Some(b'@')
+    } else if ftype.is_fifo() {
+        Some(b'|')
+    } else if ftype.is_socket() {
+        Some(b'=')
+    } else if ftype.is_file() && meta.permissions().mode() & 0o111 != 0 {
+        Some(b'*')
+    } else {
+        None
+    }
+}
+
+#[cfg(not(unix))]
+fn indicator_of(meta: &Metadata) -> Option<u8> {
+    let ftype = meta.file_type();
+    if ftype.is_dir() {
+        Some(b'/')
+    } else if ftype.is_symlink() {
+        Some(b'@')
+    } else {
+        None
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_indicator_of() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        assert_eq!(
+            indicator_of(&fs::metadata(temp_dir.path()).unwrap()),
+            Some(b'/')
+        );
+
+        let file = temp_dir.path().join("run");
+        fs::write(&file, b"").unwrap();
+        assert_eq!(indicator_of(&fs::metadata(&file).unwrap()), None);
+
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(indicator_of(&fs::metadata(&file).unwrap()), Some(b'*'));
+
+        let link = temp_dir.path().join("link");
+        std::os::unix::fs::symlink(&file, &link).unwrap();
+        assert_eq!(
+            indicator_of(&fs::symlink_metadata(&link).unwrap()),
+            Some(b'@')
+        );
+    }
+}
@@ -13,13 +13,14 @@ impl<W: Write> Buffer<W> {
         visit: &Visitor,
         path_builder: &PathBuilder,
     ) -> io::Result<()> {
-        self.write_os_string(path_builder.clone().append_relative(visit).to_os_string())?;
+        let path = path_builder.clone().append_relative(visit).to_os_string();
+        self.write_entry_name(visit, &path)?;
 
         Ok(())
     }
 
     pub fn write_dir(&mut self, meta: &Visitor, _path_builder: &PathBuilder) -> io::Result<()> {
-        self.bufwr.write_all(meta.filename().as_encoded_bytes())?;
+        self.write_entry_name(meta, meta.filename())?;
         Ok(())
     }
 
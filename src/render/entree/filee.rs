@@ -14,13 +14,14 @@ impl<W: Write> Buffer<W> {
         path_builder: &PathBuilder,
     ) -> io::Result<()> {
         // No need to pop since we clone path_builder
-        self.write_os_string(path_builder.clone().append_relative(visit).to_os_string())?;
+        let path = path_builder.clone().append_relative(visit).to_os_string();
+        self.write_entry_name(visit, &path)?;
 
         Ok(())
     }
 
     pub fn write_entry(&mut self, visit: &Visitor, _path_builder: &PathBuilder) -> io::Result<()> {
-        self.write_os_string(visit.filename().clone())?;
+        self.write_entry_name(visit, visit.filename())?;
         Ok(())
     }
 
@@ -12,14 +12,13 @@ impl<W: Write> Buffer<W> {
     pub fn write_header_relative_path(
         &mut self,
         _meta: &Metadata,
-        _root: &PathBuf,
+        root: &PathBuf,
         parent: &OsString,
     ) -> io::Result<()> {
         let mut path = PathBuf::new();
         path.push(parent);
 
-        let path = path.to_owned().into_os_string();
-        self.bufwr.write_all(path.as_encoded_bytes())?;
+        self.write_linked_name(root, path.as_os_str())?;
 
         Ok(())
     }
@@ -31,8 +30,8 @@ impl<W: Write> Buffer<W> {
     /// 1. **File:** If the `root` path represents a file, it extracts and writes the filename.
     /// 2. **Directory:** If `root` is a directory, it extracts and writes the directory name (without the trailing path separator).
     /// 3. **Fallback:** If neither filename nor directory name can be obtained (e.g., root is "." or ""),
-    ///     it converts the entire `root` path to a string and writes that. This handles edge cases that
-    ///     could otherwise cause panics.
+    ///    it converts the entire `root` path to a string and writes that. This handles edge cases that
+    ///    could otherwise cause panics.
     ///
     /// # Arguments
     ///
@@ -57,16 +56,15 @@ impl<W: Write> Buffer<W> {
     ) -> io::Result<()> {
         // Attempt to extract and write the filename
         if let Some(file_name) = root.file_name() {
-            self.bufwr.write_all(file_name.as_encoded_bytes())?;
+            self.write_linked_name(root, file_name)?;
 
         // If that fails, try extracting and writing the directory name (without the trailing separator)
         } else if let Some(folder_name) = root.file_stem() {
-            self.bufwr.write_all(folder_name.as_encoded_bytes())?;
+            self.write_linked_name(root, folder_name)?;
 
         // If neither works, fall back to writing the entire path as a string
         } else {
-            let filename = root.to_string_lossy();
-            self.bufwr.write_all(filename.as_bytes())?;
+            self.write_linked_name(root, root.as_os_str())?;
         }
 
         Ok(())
@@ -0,0 +1,91 @@
+use crate::render::buffer::Buffer;
+use crate::walk::visit::Visitor;
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::sync::OnceLock;
+
+impl<W: Write> Buffer<W> {
+    /// Write `name`, wrapped in an OSC 8 hyperlink to `path` if hyperlinks are on.
+    pub fn write_linked_name(&mut self, path: &Path, name: &OsStr) -> io::Result<()> {
+        if !self.has_hyperlinks() {
+            return self.write_name(name);
+        }
+
+        self.bufwr.write_all(b"\x1b]8;;")?;
+        self.bufwr.write_all(file_url(path).as_bytes())?;
+        self.bufwr.write_all(b"\x1b\\")?;
+        self.write_name(name)?;
+        self.bufwr.write_all(b"\x1b]8;;\x1b\\")
+    }
+
+    /// Write `name` for the entry behind `visit`, linked to its file.
+    pub fn write_entry_name(&mut self, visit: &Visitor, name: &OsStr) -> io::Result<()> {
+        match visit.absolute_path() {
+            Some(path) => self.write_linked_name(path, name),
+            None => self.write_name(name),
+        }
+    }
+}
+
+/// `file://host/absolute/path` URL of `path`, percent-encoded.
+fn file_url(path: &Path) -> String {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut url = format!("file://{}", hostname());
+
+    for &byte in path.as_os_str().as_encoded_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    url
+}
+
+/// Host name of this machine, empty if it cannot be read.
+fn hostname() -> &'static str {
+    static HOSTNAME: OnceLock<String> = OnceLock::new();
+
+    HOSTNAME.get_or_init(|| {
+        ["/proc/sys/kernel/hostname", "/etc/hostname"]
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .map(|name| name.trim().to_string())
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .unwrap_or_default()
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_linked_name() {
+        let mut buffer = Buffer::new(Vec::new()).unwrap();
+        buffer
+            .write_linked_name(Path::new("/tmp/a"), OsStr::new("a"))
+            .unwrap();
+        buffer.with_hyperlinks();
+        buffer
+            .write_linked_name(Path::new("/tmp/a"), OsStr::new("a"))
+            .unwrap();
+
+        let output = String::from_utf8(buffer.bufwr.into_inner().unwrap()).unwrap();
+        assert!(output.starts_with("a\x1b]8;;file://"));
+        assert!(output.ends_with("/tmp/a\x1b\\a\x1b]8;;\x1b\\"));
+    }
+
+    #[test]
+    fn test_file_url() {
+        let url = file_url(Path::new("/tmp/a b/ü.txt"));
+        assert!(url.starts_with("file://"));
+        assert!(url.ends_with("/tmp/a%20b/%C3%BC.txt"));
+    }
+}
@@ -1,4 +1,6 @@
+pub mod classify;
 pub mod dirr;
 pub mod filee;
 pub mod headd;
+pub mod hyperlink;
 pub mod symlinked;
@@ -13,7 +13,8 @@ impl<W: Write> Buffer<W> {
         visit: &Visitor,
         path_builder: &PathBuilder,
     ) -> io::Result<()> {
-        self.write_os_string(path_builder.clone().append_relative(visit).to_os_string())?;
+        let path = path_builder.clone().append_relative(visit).to_os_string();
+        self.write_entry_name(visit, &path)?;
         Ok(())
     }
 
@@ -22,7 +23,7 @@ impl<W: Write> Buffer<W> {
         visit: &Visitor,
         _path_builder: &PathBuilder,
     ) -> io::Result<()> {
-        self.write_os_string(visit.filename().clone())?;
+        self.write_entry_name(visit, visit.filename())?;
         Ok(())
     }
 
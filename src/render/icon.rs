@@ -0,0 +1,140 @@
+use crate::render::buffer::Buffer;
+
+use std::ffi::OsStr;
+use std::fs::Metadata;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+pub type FnIcon<W> = fn(&mut Buffer<W>, &OsStr, &Metadata) -> io::Result<()>;
+
+static DIRECTORY: &str = "\u{f07b}";
+static SYMLINK: &str = "\u{f0c1}";
+static SPECIAL: &str = "\u{f2db}";
+static EXECUTABLE: &str = "\u{f489}";
+static FILE: &str = "\u{f15b}";
+
+/// Nerd Font glyphs of well-known file names.
+static FILENAMES: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    ".git" => "\u{e5fb}",
+    ".gitignore" => "\u{f1d3}", ".gitattributes" => "\u{f1d3}", ".gitmodules" => "\u{f1d3}",
+    "Cargo.toml" => "\u{e7a8}", "Cargo.lock" => "\u{e7a8}",
+    "Dockerfile" => "\u{f308}", "docker-compose.yml" => "\u{f308}",
+    "Makefile" => "\u{e779}", "CMakeLists.txt" => "\u{e779}",
+    "LICENSE" => "\u{f02d}", "LICENSE.md" => "\u{f02d}",
+    "package.json" => "\u{e71e}",
+    "node_modules" => "\u{e5fa}",
+};
+
+/// Nerd Font glyphs of file extensions, lowercase.
+static EXTENSIONS: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "rs" => "\u{e7a8}",
+    "c" => "\u{e61e}", "h" => "\u{e61e}",
+    "cc" => "\u{e61d}", "cpp" => "\u{e61d}", "hpp" => "\u{e61d}",
+    "go" => "\u{e627}",
+    "py" => "\u{e606}",
+    "js" => "\u{e74e}", "mjs" => "\u{e74e}", "jsx" => "\u{e7ba}",
+    "ts" => "\u{e628}", "tsx" => "\u{e7ba}",
+    "java" => "\u{e738}",
+    "rb" => "\u{e21e}",
+    "lua" => "\u{e620}",
+    "sh" => "\u{f489}", "bash" => "\u{f489}", "zsh" => "\u{f489}",
+    "html" => "\u{f13b}", "htm" => "\u{f13b}",
+    "css" => "\u{e749}", "scss" => "\u{e749}",
+    "md" => "\u{f48a}", "rst" => "\u{f48a}",
+    "json" => "\u{e60b}",
+    "toml" => "\u{e615}", "ini" => "\u{e615}", "conf" => "\u{e615}",
+    "yaml" => "\u{e6a8}", "yml" => "\u{e6a8}",
+    "lock" => "\u{f023}",
+    "txt" => "\u{f15c}", "log" => "\u{f15c}",
+    "pdf" => "\u{f1c1}",
+    "zip" => "\u{f410}", "tar" => "\u{f410}", "gz" => "\u{f410}", "tgz" => "\u{f410}",
+    "xz" => "\u{f410}", "bz2" => "\u{f410}", "zst" => "\u{f410}", "7z" => "\u{f410}",
+    "png" => "\u{f1c5}", "jpg" => "\u{f1c5}", "jpeg" => "\u{f1c5}", "gif" => "\u{f1c5}",
+    "svg" => "\u{f1c5}", "webp" => "\u{f1c5}", "ico" => "\u{f1c5}",
+    "mp3" => "\u{f001}", "flac" => "\u{f001}", "wav" => "\u{f001}", "ogg" => "\u{f001}",
+    "mp4" => "\u{f03d}", "mkv" => "\u{f03d}", "webm" => "\u{f03d}", "mov" => "\u{f03d}",
+};
+
+/// Glyph of an entry, from its name first, then its kind and extension.
+pub fn icon_of(filename: &OsStr, meta: &Metadata) -> &'static str {
+    if let Some(icon) = filename.to_str().and_then(|name| FILENAMES.get(name)) {
+        return icon;
+    }
+
+    if meta.is_symlink() {
+        return SYMLINK;
+    }
+
+    if meta.is_dir() {
+        return DIRECTORY;
+    }
+
+    if !meta.is_file() {
+        return SPECIAL;
+    }
+
+    let icon = Path::new(filename)
+        .extension()
+        .and_then(OsStr::to_str)
+        .and_then(|ext| EXTENSIONS.get(ext.to_lowercase().as_str()));
+
+    match icon {
+        Some(icon) => icon,
+        None if is_executable(meta) => EXECUTABLE,
+        None => FILE,
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(meta: &Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_meta: &Metadata) -> bool {
+    false
+}
+
+impl<W: Write> Buffer<W> {
+    /// Print entry's icon, between its branch and its name
+    pub fn print_icon(
+        &mut self,
+        filename: &OsStr,
+        meta: &Metadata,
+        f: FnIcon<W>,
+    ) -> io::Result<()> {
+        f(self, filename, meta)
+    }
+
+    pub fn write_icon(&mut self, filename: &OsStr, meta: &Metadata) -> io::Result<()> {
+        self.bufwr.write_all(icon_of(filename, meta).as_bytes())?;
+        self.write_space()
+    }
+
+    pub fn write_no_icon(&mut self, _filename: &OsStr, _meta: &Metadata) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    #[test]
+    fn test_icon_of() {
+        let dir = tempfile::tempdir().unwrap();
+        let cargo = dir.path().join("Cargo.toml");
+        fs::write(&cargo, b"").unwrap();
+        let image = dir.path().join("logo.PNG");
+        fs::write(&image, b"").unwrap();
+
+        let meta = |path: &Path| fs::symlink_metadata(path).unwrap();
+        assert_eq!(icon_of(OsStr::new("Cargo.toml"), &meta(&cargo)), "\u{e7a8}");
+        assert_eq!(icon_of(OsStr::new("logo.PNG"), &meta(&image)), "\u{f1c5}");
+        assert_eq!(icon_of(OsStr::new("src"), &meta(dir.path())), DIRECTORY);
+    }
+}
@@ -0,0 +1,80 @@
+use unicode_width::UnicodeWidthStr;
+
+use std::io;
+use std::io::Write;
+
+use crate::render::buffer::Buffer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Number of terminal columns `text` occupies.
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Cuts `text` to at most `width` columns, marking the cut with `…`.
+pub fn truncate(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+
+    let mut truncated = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let ch_width = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if used + ch_width + 1 > width {
+            break;
+        }
+        truncated.push(ch);
+        used += ch_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Pads `text` with spaces up to `width` columns. Longer text is kept whole.
+pub fn pad(text: &str, width: usize, align: Align) -> String {
+    let fill = " ".repeat(width.saturating_sub(display_width(text)));
+
+    match align {
+        Align::Left => format!("{text}{fill}"),
+        Align::Right => format!("{fill}{text}"),
+    }
+}
+
+impl<W: Write> Buffer<W> {
+    /// Write a metadata cell padded to `width` columns, between single spaces.
+    ///
+    /// The tree is printed while it is walked, so each column has a fixed width
+    /// rather than one measured across all rows.
+    pub fn write_cell(&mut self, text: &str, width: usize, align: Align) -> io::Result<()> {
+        self.write_space()?;
+        self.bufwr.write_all(pad(text, width, align).as_bytes())?;
+        self.write_space()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pad_by_display_width() {
+        assert_eq!(pad("root", 6, Align::Left), "root  ");
+        assert_eq!(pad("42", 4, Align::Right), "  42");
+        assert_eq!(pad("山田", 6, Align::Left), "山田  ");
+        assert_eq!(pad("🦀x", 4, Align::Right), " 🦀x");
+        assert_eq!(pad("toolongname", 4, Align::Left), "toolongname");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 8), "short");
+        assert_eq!(truncate("Alexandria", 6), "Alexa…");
+        assert_eq!(truncate("山田太郎", 5), "山田…");
+    }
+}
@@ -3,3 +3,6 @@ pub mod attributes;
 pub mod buffer;
 pub mod color;
 pub mod entree;
+pub mod icon;
+pub mod layout;
+pub mod quote;
@@ -0,0 +1,183 @@
+use std::borrow::Cow;
+
+/// How entry names are written, so names holding control characters or
+/// invalid UTF-8 cannot corrupt the terminal or break line-based parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotingStyle {
+    /// Raw bytes, as stored on disk.
+    Literal,
+    /// Non-printable characters replaced with `?`.
+    Question,
+    /// Quoted for POSIX shells when needed, e.g. `'a b'` or `'a'$'\n''b'`.
+    ShellEscape,
+    /// Always in double quotes with C escapes, e.g. `"a\nb"`.
+    C,
+}
+
+impl QuotingStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "literal" => Some(QuotingStyle::Literal),
+            "question" => Some(QuotingStyle::Question),
+            "shell-escape" => Some(QuotingStyle::ShellEscape),
+            "c" => Some(QuotingStyle::C),
+            _ => None,
+        }
+    }
+
+    pub fn quote<'a>(&self, name: &'a [u8]) -> Cow<'a, [u8]> {
+        match self {
+            QuotingStyle::Literal => Cow::Borrowed(name),
+            QuotingStyle::Question => question(name),
+            QuotingStyle::ShellEscape => shell_escape(name),
+            QuotingStyle::C => Cow::Owned(c_quote(name)),
+        }
+    }
+}
+
+/// Splits `name` into printable text and runs of non-printable bytes.
+fn segments(name: &[u8]) -> Vec<(bool, &[u8])> {
+    let mut segments = Vec::new();
+
+    for chunk in name.utf8_chunks() {
+        let valid = chunk.valid();
+        let mut start = 0;
+        for (idx, ch) in valid.char_indices() {
+            if ch.is_control() {
+                if start < idx {
+                    segments.push((true, &valid.as_bytes()[start..idx]));
+                }
+                segments.push((false, &valid.as_bytes()[idx..idx + ch.len_utf8()]));
+                start = idx + ch.len_utf8();
+            }
+        }
+        if start < valid.len() {
+            segments.push((true, &valid.as_bytes()[start..]));
+        }
+        if !chunk.invalid().is_empty() {
+            segments.push((false, chunk.invalid()));
+        }
+    }
+
+    segments
+}
+
+fn is_printable(name: &[u8]) -> bool {
+    std::str::from_utf8(name).is_ok_and(|name| !name.chars().any(char::is_control))
+}
+
+fn question(name: &[u8]) -> Cow<'_, [u8]> {
+    if is_printable(name) {
+        return Cow::Borrowed(name);
+    }
+
+    let mut quoted = Vec::with_capacity(name.len());
+    for (printable, segment) in segments(name) {
+        if printable {
+            quoted.extend_from_slice(segment);
+        } else {
+            // One `?` per character, or per byte of invalid UTF-8.
+            let count = std::str::from_utf8(segment).map_or(segment.len(), |s| s.chars().count());
+            quoted.extend(std::iter::repeat_n(b'?', count));
+        }
+    }
+    Cow::Owned(quoted)
+}
+
+fn shell_escape(name: &[u8]) -> Cow<'_, [u8]> {
+    let needs_quotes = name.is_empty()
+        || name
+            .iter()
+            .any(|b| !(b.is_ascii_alphanumeric() || b"%+,-./:=@_^".contains(b) || *b >= 0x80))
+        || name.starts_with(b"~");
+
+    if !needs_quotes && is_printable(name) {
+        return Cow::Borrowed(name);
+    }
+
+    let mut quoted = Vec::with_capacity(name.len() + 2);
+    for (printable, segment) in segments(name) {
+        if printable {
+            quoted.push(b'\'');
+            for &byte in segment {
+                match byte {
+                    b'\'' => quoted.extend_from_slice(b"'\\''"),
+                    byte => quoted.push(byte),
+                }
+            }
+            quoted.push(b'\'');
+        } else {
+            quoted.extend_from_slice(b"$'");
+            escape_bytes(segment, &mut quoted);
+            quoted.push(b'\'');
+        }
+    }
+    if quoted.is_empty() {
+        quoted.extend_from_slice(b"''");
+    }
+    Cow::Owned(quoted)
+}
+
+fn c_quote(name: &[u8]) -> Vec<u8> {
+    let mut quoted = Vec::with_capacity(name.len() + 2);
+    quoted.push(b'"');
+    for (printable, segment) in segments(name) {
+        if printable {
+            for &byte in segment {
+                match byte {
+                    b'"' => quoted.extend_from_slice(b"\\\""),
+                    b'\\' => quoted.extend_from_slice(b"\\\\"),
+                    byte => quoted.push(byte),
+                }
+            }
+        } else {
+            escape_bytes(segment, &mut quoted);
+        }
+    }
+    quoted.push(b'"');
+    quoted
+}
+
+/// C escapes of non-printable bytes, octal when there is no short form.
+fn escape_bytes(bytes: &[u8], out: &mut Vec<u8>) {
+    for &byte in bytes {
+        match byte {
+            b'\n' => out.extend_from_slice(b"\\n"),
+            b'\t' => out.extend_from_slice(b"\\t"),
+            b'\r' => out.extend_from_slice(b"\\r"),
+            0x1b => out.extend_from_slice(b"\\e"),
+            byte => out.extend_from_slice(format!("\\{byte:03o}").as_bytes()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(style: QuotingStyle, name: &[u8]) -> String {
+        String::from_utf8(style.quote(name).into_owned()).unwrap()
+    }
+
+    #[test]
+    fn test_quoting_styles() {
+        let name = b"a\nb\x1b[31m\xff\xc3\xa9";
+
+        assert_eq!(QuotingStyle::Literal.quote(name).as_ref(), name);
+        assert_eq!(quote(QuotingStyle::Question, name), "a?b?[31m?é");
+        assert_eq!(
+            quote(QuotingStyle::ShellEscape, name),
+            "'a'$'\\n''b'$'\\e''[31m'$'\\377''é'"
+        );
+        assert_eq!(quote(QuotingStyle::C, name), "\"a\\nb\\e[31m\\377é\"");
+    }
+
+    #[test]
+    fn test_shell_escape_plain_names() {
+        assert_eq!(quote(QuotingStyle::ShellEscape, b"main.rs"), "main.rs");
+        assert_eq!(quote(QuotingStyle::ShellEscape, b"caf\xc3\xa9"), "café");
+        assert_eq!(quote(QuotingStyle::ShellEscape, b"a b"), "'a b'");
+        assert_eq!(quote(QuotingStyle::ShellEscape, b"it's"), "'it'\\''s'");
+        assert_eq!(quote(QuotingStyle::Question, b"plain"), "plain");
+    }
+}
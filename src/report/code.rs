@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::Path;
+
+static LANGUAGES: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "rs" => "Rust",
+    "c" => "C", "h" => "C",
+    "cc" => "C++", "cpp" => "C++", "cxx" => "C++", "hpp" => "C++", "hh" => "C++",
+    "go" => "Go",
+    "py" => "Python",
+    "js" => "JavaScript", "mjs" => "JavaScript", "cjs" => "JavaScript", "jsx" => "JavaScript",
+    "ts" => "TypeScript", "tsx" => "TypeScript",
+    "java" => "Java",
+    "kt" => "Kotlin", "kts" => "Kotlin",
+    "scala" => "Scala",
+    "swift" => "Swift",
+    "cs" => "C#",
+    "rb" => "Ruby",
+    "php" => "PHP",
+    "lua" => "Lua",
+    "zig" => "Zig",
+    "hs" => "Haskell",
+    "ml" => "OCaml", "mli" => "OCaml",
+    "ex" => "Elixir", "exs" => "Elixir",
+    "erl" => "Erlang",
+    "sh" => "Shell", "bash" => "Shell", "zsh" => "Shell",
+    "sql" => "SQL",
+    "html" => "HTML", "htm" => "HTML",
+    "css" => "CSS", "scss" => "CSS",
+    "md" => "Markdown",
+    "toml" => "TOML",
+    "yaml" => "YAML", "yml" => "YAML",
+    "json" => "JSON",
+    "xml" => "XML",
+};
+
+/// Language of a source file, detected from its extension.
+pub fn language_of(path: &Path) -> Option<&'static str> {
+    let ext = path.extension().and_then(OsStr::to_str)?;
+    LANGUAGES
+        .get(ext)
+        .or_else(|| LANGUAGES.get(ext.to_lowercase().as_str()))
+        .copied()
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct LanguageStats {
+    files: usize,
+    lines: u64,
+}
+
+/// Line counts grouped by language for `--code-stats`.
+#[derive(Debug, Default)]
+pub struct CodeStats {
+    enabled: bool,
+    languages: BTreeMap<&'static str, LanguageStats>,
+}
+
+impl CodeStats {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn add(&mut self, path: &Path, lines: u64) {
+        if !self.enabled {
+            return;
+        }
+
+        if let Some(language) = language_of(path) {
+            let stats = self.languages.entry(language).or_default();
+            stats.files += 1;
+            stats.lines += lines;
+        }
+    }
+
+    /// Table of languages, the one with the most lines first.
+    pub fn to_table(&self) -> Vec<String> {
+        let mut rows: Vec<(&str, LanguageStats)> =
+            self.languages.iter().map(|(l, s)| (*l, *s)).collect();
+        rows.sort_by_key(|row| std::cmp::Reverse(row.1.lines));
+
+        let total = rows
+            .iter()
+            .fold(LanguageStats::default(), |acc, (_, s)| LanguageStats {
+                files: acc.files + s.files,
+                lines: acc.lines + s.lines,
+            });
+
+        let mut table = Vec::with_capacity(rows.len() + 2);
+        table.push(format!("{:<12} {:>8} {:>10}", "Language", "Files", "Lines"));
+        for (language, stats) in rows {
+            table.push(format!(
+                "{:<12} {:>8} {:>10}",
+                language, stats.files, stats.lines
+            ));
+        }
+        table.push(format!(
+            "{:<12} {:>8} {:>10}",
+            "Total", total.files, total.lines
+        ));
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_of() {
+        assert_eq!(language_of(Path::new("src/main.rs")), Some("Rust"));
+        assert_eq!(language_of(Path::new("include/a.H")), Some("C"));
+        assert_eq!(language_of(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn test_to_table() {
+        let mut stats = CodeStats::default();
+        stats.add(Path::new("a.rs"), 10);
+        assert!(stats.to_table().len() == 2);
+
+        stats.enable();
+        stats.add(Path::new("a.rs"), 10);
+        stats.add(Path::new("b.rs"), 5);
+        stats.add(Path::new("c.py"), 30);
+        stats.add(Path::new("README"), 99);
+
+        let table = stats.to_table();
+        assert_eq!(table.len(), 4);
+        assert!(table[1].starts_with("Python"));
+        assert!(table[2].starts_with("Rust"));
+        assert_eq!(table[3], format!("{:<12} {:>8} {:>10}", "Total", 3, 45));
+    }
+}
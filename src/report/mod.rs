@@ -1 +1,2 @@
+pub mod code;
 pub mod stats;
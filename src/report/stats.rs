@@ -1,19 +1,46 @@
+use crate::config::category::Categories;
+use crate::walk::verify::Status;
+
 #[derive(PartialEq)]
 pub enum ReportMode {
     Default,
     Exhaustive,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Outcome of `--verify`, counted in files.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VerifyCounts {
+    ok: usize,
+    modified: usize,
+    missing: usize,
+    extra: usize,
+}
+
+/// Number of files and their total size in one category.
+#[derive(Debug, Clone)]
+pub struct CategoryStats {
+    name: String,
+    files: usize,
+    size: u64,
+}
+
+#[derive(Debug, Clone)]
 pub struct DirectoryStats {
     directories: usize,
     files: usize,
-    media: usize,
+    /// Same order as the `Categories` entries are classified into.
+    categories: Vec<CategoryStats>,
     hidden_files: usize,
     symlinks: usize,
+    /// Symlinks whose target does not exist, also counted in `symlinks`.
+    broken_symlinks: usize,
     special_files: usize,
     total_items: usize,
     size: u64,
+    /// Allocated size on disk, only tracked when `--disk-usage` is requested.
+    disk_usage: Option<u64>,
+    /// Only tracked when a manifest is verified.
+    verify: Option<VerifyCounts>,
     err_directories: u64,
 }
 
@@ -22,10 +49,13 @@ impl Default for DirectoryStats {
         DirectoryStats {
             directories: 1,
             files: 0,
-            media: 0,
+            categories: Vec::new(),
             size: 0,
+            disk_usage: None,
+            verify: None,
             hidden_files: 0,
             symlinks: 0,
+            broken_symlinks: 0,
             total_items: 0,
             special_files: 0,
             err_directories: 0,
@@ -50,16 +80,61 @@ impl DirectoryStats {
         self.symlinks += 1
     }
 
+    pub fn broken_symlink_add_one(&mut self) {
+        self.broken_symlinks += 1
+    }
+
     pub fn add_size(&mut self, size: u64) {
         self.size += size
     }
 
+    /// Start accumulating allocated size alongside the apparent size.
+    pub fn with_disk_usage(&mut self) {
+        self.disk_usage = Some(0);
+    }
+
+    pub fn add_disk_usage(&mut self, size: u64) {
+        if let Some(disk_usage) = self.disk_usage.as_mut() {
+            *disk_usage += size
+        }
+    }
+
+    /// Start counting verification results.
+    pub fn with_verify(&mut self) {
+        self.verify = Some(VerifyCounts::default());
+    }
+
+    pub fn verify_add(&mut self, status: Status, count: usize) {
+        if let Some(verify) = self.verify.as_mut() {
+            match status {
+                Status::Ok => verify.ok += count,
+                Status::Modified => verify.modified += count,
+                Status::Missing => verify.missing += count,
+                Status::Extra => verify.extra += count,
+            }
+        }
+    }
+
     pub fn special_add_one(&mut self) {
         self.special_files += 1
     }
 
-    pub fn media_add_one(&mut self) {
-        self.media += 1
+    pub fn with_categories(&mut self, categories: &Categories) {
+        self.categories = categories
+            .iter()
+            .map(|category| CategoryStats {
+                name: category.name().to_string(),
+                files: 0,
+                size: 0,
+            })
+            .collect();
+    }
+
+    pub fn category_add_one(&mut self, index: usize, size: u64) {
+        if let Some(category) = self.categories.get_mut(index) {
+            category.files += 1;
+            category.size += size;
+        }
     }
 
     pub fn err_dirs_add_one(&mut self) {
@@ -74,7 +149,6 @@ impl DirectoryStats {
             + self.files
             + self.symlinks
             + self.special_files
-            + self.media
             + self.err_directories as usize
             + self.special_files;
     }
@@ -119,12 +193,10 @@ impl DirectoryStats {
         // let special_files = self.special_files_to_string(&report_mode).unwrap();
         // let special_files = format!("{}: {}", special_files.1, special_files.0);
 
-        let media_files = format!("M: {}", self.media);
-
         let total_items = self.total_items_to_string(&report_mode).unwrap();
         let total_items = format!("{}: {}", total_items.1, total_items.0);
 
-        let size = self.size_to_string(&report_mode).unwrap();
+        let size = self.size_to_string(self.size, &report_mode).unwrap();
         let size_str: String = if report_mode == ReportMode::Default {
             "SZ".to_string()
         } else {
@@ -136,10 +208,51 @@ impl DirectoryStats {
         report_summary.push(files);
         report_summary.push(hidden_files);
         report_summary.push(symlinks);
+        if self.broken_symlinks > 0 {
+            let broken_str = if report_mode == ReportMode::Default {
+                "BR"
+            } else {
+                "Broken"
+            };
+            report_summary.push(format!("{}: {}", broken_str, self.broken_symlinks));
+        }
         // report_summary.push(special_files);
-        report_summary.push(media_files);
         report_summary.push(total_items);
         report_summary.push(size);
+
+        for category in self.categories.iter().filter(|c| c.files > 0) {
+            let size = self.size_to_string(category.size, &report_mode).unwrap();
+            report_summary.push(format!(
+                "{}: {} ({} {})",
+                category.name, category.files, size.0, size.1
+            ));
+        }
+
+        if let Some(disk_usage) = self.disk_usage {
+            let disk_usage = self.size_to_string(disk_usage, &report_mode).unwrap();
+            let disk_usage_str = if report_mode == ReportMode::Default {
+                "DU"
+            } else {
+                "Disk Usage"
+            };
+            report_summary.push(format!(
+                "{}: {} {}",
+                disk_usage_str, disk_usage.0, disk_usage.1
+            ));
+        }
+
+        if let Some(verify) = self.verify {
+            let labels = if report_mode == ReportMode::Default {
+                ["OK", "MOD", "MISS", "EXTRA"]
+            } else {
+                ["Verified", "Modified", "Missing", "Extra"]
+            };
+            let counts = [verify.ok, verify.modified, verify.missing, verify.extra];
+
+            for (label, count) in labels.iter().zip(counts) {
+                report_summary.push(format!("{}: {}", label, count));
+            }
+        }
     }
 }
 
@@ -262,8 +375,12 @@ impl DirectoryStats {
         Ok((total_items_count, total_items_str))
     }
 
-    fn size_to_string(&self, report_mode: &ReportMode) -> anyhow::Result<(String, String)> {
-        let size = self.size as f64;
+    fn size_to_string(
+        &self,
+        size: u64,
+        report_mode: &ReportMode,
+    ) -> anyhow::Result<(String, String)> {
+        let size = size as f64;
         let size_count: f64;
 
         let mut unit_count = String::new();
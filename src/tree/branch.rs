@@ -1,25 +1,85 @@
+use crate::config::theme::BranchGlyphs;
 use crate::render::buffer::IntoBranch;
 
+use std::borrow::Cow;
 use std::io::StdoutLock;
 
 #[derive(Debug, Clone)]
 pub struct Branch {
     /// Represents the end of a branch, e.g., "└── "
-    end: &'static str,
+    end: Cow<'static, str>,
     /// Represents the middle part of a branch, e.g., "├── "
-    middle: &'static str,
+    middle: Cow<'static, str>,
     /// Represents empty space between branches, e.g., "    "
-    space: &'static str,
+    space: Cow<'static, str>,
     /// Represents the main structural part of the tree, e.g., "│   "
-    structural: &'static str,
+    structural: Cow<'static, str>,
+}
+
+/// Box-drawing style of the branches, as given to `--charset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Ascii,
+    Utf8,
+    Heavy,
+    Rounded,
+    Double,
+}
+
+impl Charset {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ascii" => Some(Charset::Ascii),
+            "utf8" | "utf-8" => Some(Charset::Utf8),
+            "heavy" => Some(Charset::Heavy),
+            "rounded" => Some(Charset::Rounded),
+            "double" => Some(Charset::Double),
+            _ => None,
+        }
+    }
+
+    /// Vertical line, tee, last corner and horizontal line.
+    #[rustfmt::skip]
+    fn glyphs(&self) -> (char, char, char, char) {
+        match self {
+            Charset::Ascii   => ('|', '|', '`', '-'),
+            Charset::Utf8    => ('│', '├', '└', '─'),
+            Charset::Heavy   => ('┃', '┣', '┗', '━'),
+            Charset::Rounded => ('│', '├', '╰', '─'),
+            Charset::Double  => ('║', '╠', '╚', '═'),
+        }
+    }
 }
 
 impl Branch {
+    /// Smallest indent able to hold a corner and the gap before the name.
+    pub const MIN_INDENT: usize = 2;
+
+    /// Draws the branches with `charset`, indenting each level by `width` columns.
+    pub fn with_charset(&mut self, charset: Charset, width: usize) {
+        let width = width.max(Self::MIN_INDENT);
+        let (vertical, tee, corner, horizontal) = charset.glyphs();
+        let line: String = std::iter::repeat_n(horizontal, width - 2).collect();
+        let gap = " ".repeat(width - 1);
+
+        self.end = Cow::Owned(format!("{corner}{line} "));
+        self.middle = Cow::Owned(format!("{tee}{line} "));
+        self.space = Cow::Owned(" ".repeat(width));
+        self.structural = Cow::Owned(format!("{vertical}{gap}"));
+    }
+
     pub fn no_branch(&mut self) {
-        self.end = "";
-        self.middle = "";
-        self.space = "";
-        self.structural = "";
+        self.end = Cow::Borrowed("");
+        self.middle = Cow::Borrowed("");
+        self.space = Cow::Borrowed("");
+        self.structural = Cow::Borrowed("");
+    }
+
+    pub fn with_glyphs(&mut self, glyphs: &BranchGlyphs) {
+        self.end = Cow::Owned(glyphs.end.clone());
+        self.middle = Cow::Owned(glyphs.middle.clone());
+        self.space = Cow::Owned(glyphs.space.clone());
+        self.structural = Cow::Owned(glyphs.structural.clone());
     }
 }
 
@@ -27,10 +87,10 @@ impl Default for Branch {
     #[rustfmt::skip]
     fn default() -> Self {
         Branch {
-            end:        "└── ",
-            middle:     "├── ",
-            space:      "    ",
-            structural: "│   ",
+            end:        Cow::Borrowed("└── "),
+            middle:     Cow::Borrowed("├── "),
+            space:      Cow::Borrowed("    "),
+            structural: Cow::Borrowed("│   "),
         }
     }
 }
@@ -59,17 +119,46 @@ impl PaintBranch for Branch {
     {
         if value_has_next {
             if value_is_one {
-                buffer.print_branch(self.structural)?;
+                buffer.print_branch(&self.structural)?;
             } else {
-                buffer.print_branch(self.space)?;
+                buffer.print_branch(&self.space)?;
             }
         } else {
             if value_is_one {
-                buffer.print_branch(self.middle)?;
+                buffer.print_branch(&self.middle)?;
             } else {
-                buffer.print_branch(self.end)?;
+                buffer.print_branch(&self.end)?;
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_charset() {
+        let mut branch = Branch::default();
+        branch.with_charset(Charset::Utf8, 4);
+        let default = Branch::default();
+        assert_eq!(branch.end, default.end);
+        assert_eq!(branch.middle, default.middle);
+        assert_eq!(branch.space, default.space);
+        assert_eq!(branch.structural, default.structural);
+
+        branch.with_charset(Charset::Ascii, 4);
+        assert_eq!(branch.end, "`-- ");
+        assert_eq!(branch.middle, "|-- ");
+        assert_eq!(branch.structural, "|   ");
+
+        branch.with_charset(Charset::Rounded, 2);
+        assert_eq!(branch.end, "╰ ");
+        assert_eq!(branch.space, "  ");
+        assert_eq!(branch.structural, "│ ");
+
+        branch.with_charset(Charset::Double, 6);
+        assert_eq!(branch.middle, "╠════ ");
+    }
+}
@@ -40,6 +40,11 @@ impl Level {
     pub fn can_descend_further(&self) -> bool {
         self.lvl < self.cap
     }
+
+    /// Number of levels still printed below the current one.
+    pub fn remaining(&self) -> i32 {
+        self.cap - self.lvl
+    }
 }
 
 #[cfg(test)]
@@ -47,7 +47,7 @@ impl Node {
     fn next_iter<'a>(
         &'a self,
         iter: &mut impl Iterator<Item = (usize, &'a i32)>,
-    ) -> Option<(usize, &i32)> {
+    ) -> Option<(usize, &'a i32)> {
         iter.next()
     }
 
@@ -0,0 +1,250 @@
+use sha2::Digest as _;
+
+use std::fs;
+use std::fs::File;
+use std::fs::Metadata;
+use std::io;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashAlgo {
+    Sha256,
+    Blake3,
+    Xxh3,
+}
+
+impl HashAlgo {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(HashAlgo::Sha256),
+            "blake3" => Some(HashAlgo::Blake3),
+            "xxh3" => Some(HashAlgo::Xxh3),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgo::Sha256 => "sha256",
+            HashAlgo::Blake3 => "blake3",
+            HashAlgo::Xxh3 => "xxh3",
+        }
+    }
+
+    /// Number of hex characters in a digest produced by this algorithm.
+    pub fn hex_len(&self) -> usize {
+        match self {
+            HashAlgo::Sha256 | HashAlgo::Blake3 => 64,
+            HashAlgo::Xxh3 => 16,
+        }
+    }
+
+    /// Returns the lowercase hex digest of everything `reader` yields.
+    pub fn digest<R: Read>(&self, mut reader: R) -> io::Result<String> {
+        let mut chunk = vec![0; 64 * 1024];
+
+        match self {
+            HashAlgo::Sha256 => {
+                let mut hasher = sha2::Sha256::new();
+                loop {
+                    match reader.read(&mut chunk)? {
+                        0 => break,
+                        n => hasher.update(&chunk[..n]),
+                    }
+                }
+                Ok(format!("{:x}", hasher.finalize()))
+            }
+            HashAlgo::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                loop {
+                    match reader.read(&mut chunk)? {
+                        0 => break,
+                        n => hasher.update(&chunk[..n]),
+                    };
+                }
+                Ok(hasher.finalize().to_hex().to_string())
+            }
+            HashAlgo::Xxh3 => {
+                let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+                loop {
+                    match reader.read(&mut chunk)? {
+                        0 => break,
+                        n => hasher.update(&chunk[..n]),
+                    }
+                }
+                Ok(format!("{:016x}", hasher.digest()))
+            }
+        }
+    }
+
+    pub fn digest_file(&self, path: &Path) -> io::Result<String> {
+        self.digest(File::open(path)?)
+    }
+}
+
+/// Comment line naming the algorithm a manifest was written with.
+pub static ALGORITHM_COMMENT: &str = "# algorithm: ";
+/// Comment line giving the size of the file listed on the next line.
+pub static SIZE_COMMENT: &str = "# size ";
+
+/// Fingerprints regular files while the tree is walked and optionally
+/// records them in a `sha256sum`-compatible manifest.
+///
+/// The algorithm and each file's size go on `#` comment lines, which
+/// `sha256sum -c` skips.
+#[derive(Debug, Default)]
+pub struct Digest {
+    algo: Option<HashAlgo>,
+    manifest: Option<BufWriter<File>>,
+    /// `(dev, inode)` of the manifest, which must not list itself.
+    manifest_id: Option<(u64, u64)>,
+    /// Digest of the entry hashed last, so `--verify` need not read it again.
+    last: Option<String>,
+}
+
+impl Digest {
+    pub fn with_algo(&mut self, algo: HashAlgo) {
+        self.algo = Some(algo);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.algo.is_some()
+    }
+
+    /// Write `<digest>  <path>` lines to `path`, using SHA-256 unless another
+    /// algorithm was requested.
+    pub fn with_manifest(&mut self, path: &Path) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let file = fs::File::create(path)
+            .with_context(|| format!("Cannot create manifest '{}'", path.display()))?;
+        self.manifest_id = file.metadata().ok().as_ref().and_then(file_id);
+        let algo = *self.algo.get_or_insert(HashAlgo::Sha256);
+
+        let mut manifest = BufWriter::new(file);
+        writeln!(manifest, "{ALGORITHM_COMMENT}{}", algo.name())?;
+        self.manifest = Some(manifest);
+        Ok(())
+    }
+
+    pub fn algo(&self) -> Option<HashAlgo> {
+        self.algo
+    }
+
+    /// Digest of the entry passed to the latest `compute`, if it was hashed.
+    pub fn last(&self) -> Option<&str> {
+        self.last.as_deref()
+    }
+
+    /// Hashes the entry at `path` if it is a regular file, recording it in
+    /// the manifest under `relative_path`. Unreadable files are skipped.
+    pub fn compute(
+        &mut self,
+        meta: &Metadata,
+        path: &Path,
+        relative_path: &Path,
+    ) -> io::Result<Option<String>> {
+        self.last = None;
+
+        let Some(algo) = self.algo else {
+            return Ok(None);
+        };
+
+        if !meta.is_file() || (self.manifest_id.is_some() && file_id(meta) == self.manifest_id) {
+            return Ok(None);
+        }
+
+        let Ok(sum) = algo.digest_file(path) else {
+            return Ok(None);
+        };
+
+        if let Some(manifest) = self.manifest.as_mut() {
+            let path = relative_path.as_os_str().as_encoded_bytes();
+            // Like `sha256sum`, a leading backslash marks an escaped path so
+            // names with newlines keep the manifest one entry per line.
+            let escaped = path.iter().any(|b| matches!(b, b'\\' | b'\n' | b'\r'));
+            writeln!(manifest, "{SIZE_COMMENT}{}", meta.len())?;
+            if escaped {
+                manifest.write_all(b"\\")?;
+            }
+            manifest.write_all(sum.as_bytes())?;
+            manifest.write_all(b"  ")?;
+            if escaped {
+                for &byte in path {
+                    match byte {
+                        b'\\' => manifest.write_all(b"\\\\")?,
+                        b'\n' => manifest.write_all(b"\\n")?,
+                        b'\r' => manifest.write_all(b"\\r")?,
+                        byte => manifest.write_all(&[byte])?,
+                    }
+                }
+            } else {
+                manifest.write_all(path)?;
+            }
+            manifest.write_all(b"\n")?;
+        }
+
+        self.last = Some(sum.clone());
+        Ok(Some(sum))
+    }
+
+    /// Column filler for entries without a digest, as wide as a real digest.
+    pub fn placeholder(&self) -> String {
+        let len = self.algo.map(|algo| algo.hex_len()).unwrap_or(0);
+        format!("{:<len$}", "─")
+    }
+
+    pub fn finish(&mut self) -> io::Result<()> {
+        if let Some(manifest) = self.manifest.as_mut() {
+            manifest.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// `(dev, inode)` identifying the file behind `meta`.
+#[cfg(unix)]
+pub fn file_id(meta: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+pub fn file_id(_meta: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests() {
+        let input = b"abc".as_slice();
+
+        assert_eq!(
+            HashAlgo::Sha256.digest(input).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            HashAlgo::Blake3.digest(input).unwrap(),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        assert_eq!(HashAlgo::Xxh3.digest(input).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(HashAlgo::from_name("sha256"), Some(HashAlgo::Sha256));
+        assert_eq!(HashAlgo::from_name("blake3"), Some(HashAlgo::Blake3));
+        assert_eq!(HashAlgo::from_name("xxh3"), Some(HashAlgo::Xxh3));
+        assert_eq!(HashAlgo::from_name("md5"), None);
+        assert_eq!(
+            HashAlgo::from_name(HashAlgo::Blake3.name()),
+            Some(HashAlgo::Blake3)
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::fs::Metadata;
+use std::path::Path;
+
+/// Directories on the way from the root to the one being walked, so
+/// `--follow` can refuse symlinks leading back into one of them.
+#[derive(Debug, Default, Clone)]
+pub struct Follow {
+    enabled: bool,
+    /// `(dev, inode)` of each ancestor, `None` when it could not be read.
+    ancestors: Vec<Option<(u64, u64)>>,
+}
+
+impl Follow {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records `path` as the directory being walked, undone by `leave`.
+    pub fn enter(&mut self, path: &Path) {
+        if self.enabled {
+            self.ancestors
+                .push(std::fs::metadata(path).ok().as_ref().and_then(file_id));
+        }
+    }
+
+    pub fn leave(&mut self) {
+        if self.enabled {
+            self.ancestors.pop();
+        }
+    }
+
+    /// Whether descending into the directory behind `meta` would loop.
+    pub fn loops(&self, meta: &Metadata) -> bool {
+        file_id(meta).is_some_and(|id| self.ancestors.contains(&Some(id)))
+    }
+}
+
+#[cfg(unix)]
+fn file_id(meta: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_loops() {
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let inner = temp_dir.path().join("inner");
+        fs::create_dir(&inner).expect("Failed to create directory");
+        std::os::unix::fs::symlink(temp_dir.path(), inner.join("up"))
+            .expect("Failed to create symlink");
+
+        let mut follow = Follow::default();
+        follow.enable();
+        follow.enter(temp_dir.path());
+        follow.enter(&inner);
+
+        let up = fs::metadata(inner.join("up")).unwrap();
+        assert!(follow.loops(&up));
+
+        follow.leave();
+        follow.leave();
+        assert!(!follow.loops(&up));
+    }
+}
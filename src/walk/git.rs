@@ -0,0 +1,596 @@
+#[cfg(feature = "git")]
+use git2::Repository;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+#[cfg(feature = "git")]
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+#[cfg(feature = "git")]
+use std::time::Duration;
+use std::time::SystemTime;
+#[cfg(feature = "git")]
+use std::time::UNIX_EPOCH;
+
+/// Status of an entry in the working tree, ordered by how much attention it
+/// needs so directories can show the most important status below them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GitStatus {
+    Clean,
+    Ignored,
+    Untracked,
+    Staged,
+    Modified,
+    Conflicted,
+}
+
+impl GitStatus {
+    pub fn marker(&self) -> &'static str {
+        match self {
+            GitStatus::Clean => "-",
+            GitStatus::Ignored => "!",
+            GitStatus::Untracked => "?",
+            GitStatus::Staged => "S",
+            GitStatus::Modified => "M",
+            GitStatus::Conflicted => "U",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitStatus::Clean => "clean",
+            GitStatus::Ignored => "ignored",
+            GitStatus::Untracked => "untracked",
+            GitStatus::Staged => "staged",
+            GitStatus::Modified => "modified",
+            GitStatus::Conflicted => "conflicted",
+        }
+    }
+
+    /// Picks the most important of the index and working tree flags.
+    #[cfg(feature = "git")]
+    fn from_flags(flags: git2::Status) -> Self {
+        use git2::Status;
+
+        if flags.is_conflicted() {
+            GitStatus::Conflicted
+        } else if flags.intersects(
+            Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_TYPECHANGE | Status::WT_RENAMED,
+        ) {
+            GitStatus::Modified
+        } else if flags.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            GitStatus::Staged
+        } else if flags.is_wt_new() {
+            GitStatus::Untracked
+        } else if flags.is_ignored() {
+            GitStatus::Ignored
+        } else {
+            GitStatus::Clean
+        }
+    }
+}
+
+/// Working tree status of every changed, untracked or ignored path, read
+/// once from the local repository's index and working tree when `--git` is
+/// requested.
+#[derive(Debug, Default)]
+pub struct GitStatuses {
+    /// Location of the walked root inside the repository.
+    prefix: Option<PathBuf>,
+    /// Keyed by path relative to the repository root.
+    entries: HashMap<PathBuf, GitStatus>,
+    /// Aggregated status of directories containing changes.
+    dirs: HashMap<PathBuf, GitStatus>,
+    /// Ignored directories, whose content is not listed by git.
+    ignored_dirs: Vec<PathBuf>,
+}
+
+impl GitStatuses {
+    /// Reads the status of the repository containing `root`. Leaves the
+    /// statuses empty if `root` is not inside a repository.
+    #[cfg(feature = "git")]
+    pub fn load(&mut self, root: &Path) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let Some((repo, prefix)) = open_repo(root) else {
+            return Ok(());
+        };
+
+        let mut options = git2::StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(true)
+            .recurse_ignored_dirs(false);
+
+        let statuses = repo
+            .statuses(Some(&mut options))
+            .with_context(|| format!("Cannot read git status of '{}'", root.display()))?;
+
+        for entry in statuses.iter() {
+            self.insert(
+                path_from_bytes(entry.path_bytes()),
+                GitStatus::from_flags(entry.status()),
+            );
+        }
+
+        self.prefix = Some(prefix);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "git"))]
+    pub fn load(&mut self, _root: &Path) -> anyhow::Result<()> {
+        Err(without_git("--git"))
+    }
+
+    /// Whether the walked root is inside a repository whose status was read.
+    pub fn is_loaded(&self) -> bool {
+        self.prefix.is_some()
+    }
+
+    #[cfg_attr(not(feature = "git"), allow(dead_code))]
+    fn insert(&mut self, path: PathBuf, status: GitStatus) {
+        // Git marks ignored directories with a trailing slash.
+        if status == GitStatus::Ignored && path.as_os_str().as_encoded_bytes().ends_with(b"/") {
+            self.ignored_dirs.push(path.clone());
+        }
+
+        if status != GitStatus::Ignored {
+            for dir in path.ancestors().skip(1) {
+                let aggregated = self.dirs.entry(dir.to_path_buf()).or_insert(status);
+                *aggregated = (*aggregated).max(status);
+            }
+        }
+
+        self.entries.insert(path, status);
+    }
+
+    /// Status of `path`, a path below the walked `root`.
+    ///
+    /// Returns `None` if the root is not inside a repository.
+    pub fn status(&self, path: &Path, root: &Path) -> Option<GitStatus> {
+        let prefix = self.prefix.as_ref()?;
+        let relative = path.strip_prefix(root).ok()?;
+        let path = prefix.join(relative);
+
+        if let Some(status) = self.entries.get(&path) {
+            return Some(*status);
+        }
+
+        if let Some(status) = self.dirs.get(&path) {
+            return Some(*status);
+        }
+
+        if self.ignored_dirs.iter().any(|dir| path.starts_with(dir)) {
+            return Some(GitStatus::Ignored);
+        }
+
+        Some(GitStatus::Clean)
+    }
+}
+
+/// The last commit that touched an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastCommit {
+    pub hash: String,
+    pub author: String,
+    pub time: SystemTime,
+}
+
+/// Last commit of every path in the walked tree, read once from the local
+/// history when `--git-log` is requested.
+#[derive(Debug, Default)]
+pub struct GitHistory {
+    /// Location of the walked root inside the repository.
+    prefix: Option<PathBuf>,
+    /// Keyed by path relative to the repository root, for files and the
+    /// directories containing them.
+    commits: HashMap<PathBuf, LastCommit>,
+    /// Displayed paths still waiting for their last commit while loading.
+    pending: Pending,
+}
+
+/// Tracked paths down to the displayed depth that no commit touched yet.
+#[derive(Debug, Default)]
+struct Pending {
+    /// Tracked directories to their children.
+    children: HashMap<PathBuf, Vec<PathBuf>>,
+    paths: HashSet<PathBuf>,
+    /// Pending paths without a pending ancestor, which the diffs are limited to.
+    frontier: HashSet<PathBuf>,
+}
+
+#[cfg_attr(not(feature = "git"), allow(dead_code))]
+impl Pending {
+    /// Lists `tree`, found at `dir`, and what it contains down to `depth` levels.
+    #[cfg(feature = "git")]
+    fn new(
+        repo: &Repository,
+        tree: &git2::Tree,
+        dir: &Path,
+        depth: usize,
+    ) -> Result<Self, git2::Error> {
+        let mut pending = Pending::default();
+        pending.paths.insert(dir.to_path_buf());
+        pending.frontier.insert(dir.to_path_buf());
+        pending.collect(repo, tree, dir, depth)?;
+        Ok(pending)
+    }
+
+    #[cfg(feature = "git")]
+    fn collect(
+        &mut self,
+        repo: &Repository,
+        tree: &git2::Tree,
+        dir: &Path,
+        depth: usize,
+    ) -> Result<(), git2::Error> {
+        if depth == 0 {
+            return Ok(());
+        }
+
+        let mut children = Vec::with_capacity(tree.len());
+        for entry in tree.iter() {
+            let path = dir.join(path_from_bytes(entry.name_bytes()));
+            if entry.kind() == Some(git2::ObjectType::Tree) {
+                let subtree = entry.to_object(repo)?.peel_to_tree()?;
+                self.collect(repo, &subtree, &path, depth - 1)?;
+            }
+            self.paths.insert(path.clone());
+            children.push(path);
+        }
+        self.children.insert(dir.to_path_buf(), children);
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Marks `path` as done. Returns whether the frontier moved, so the
+    /// diffs can be narrowed to what is still pending.
+    fn resolve(&mut self, path: &Path) -> bool {
+        if !self.paths.remove(path) || !self.frontier.remove(path) {
+            return false;
+        }
+
+        let mut stack = vec![path.to_path_buf()];
+        while let Some(dir) = stack.pop() {
+            for child in self.children.get(&dir).into_iter().flatten() {
+                if self.paths.contains(child) {
+                    self.frontier.insert(child.clone());
+                } else {
+                    stack.push(child.clone());
+                }
+            }
+        }
+        true
+    }
+
+    /// Diff options matching only the frontier, taken literally.
+    #[cfg(feature = "git")]
+    fn diff_options(&self) -> git2::DiffOptions {
+        let mut options = git2::DiffOptions::new();
+        options.disable_pathspec_match(true);
+        // An empty path is the repository root, which matches everything.
+        if !self.frontier.iter().any(|path| path.as_os_str().is_empty()) {
+            for path in &self.frontier {
+                options.pathspec(path);
+            }
+        }
+        options
+    }
+}
+
+impl GitHistory {
+    /// Reads the history of the walked `root` for the entries up to `depth`
+    /// levels below it. Leaves the history empty if `root` is not inside a
+    /// repository.
+    #[cfg(feature = "git")]
+    pub fn load(&mut self, root: &Path, depth: usize) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let Some((repo, prefix)) = open_repo(root) else {
+            return Ok(());
+        };
+
+        self.walk(&repo, &prefix, depth)
+            .with_context(|| format!("Cannot read git history of '{}'", root.display()))?;
+        self.pending = Pending::default();
+        self.prefix = Some(prefix);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "git"))]
+    pub fn load(&mut self, _root: &Path, _depth: usize) -> anyhow::Result<()> {
+        Err(without_git("--git-log"))
+    }
+
+    /// Goes through the history newest commit first, like `git log`, and
+    /// records the displayed paths each non-merge commit changed below
+    /// `prefix`. Stops once every tracked path up to `depth` has its commit.
+    #[cfg(feature = "git")]
+    fn walk(&mut self, repo: &Repository, prefix: &Path, depth: usize) -> Result<(), git2::Error> {
+        let Ok(head) = repo.head().and_then(|head| head.peel_to_tree()) else {
+            // A repository without any commit yet has no history.
+            return Ok(());
+        };
+        let tree = if prefix.as_os_str().is_empty() {
+            head
+        } else {
+            match head
+                .get_path(prefix)
+                .and_then(|entry| entry.to_object(repo))
+            {
+                Ok(object) => object.peel_to_tree()?,
+                // Nothing below an untracked root has a commit.
+                Err(_) => return Ok(()),
+            }
+        };
+        self.pending = Pending::new(repo, &tree, prefix, depth)?;
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TIME)?;
+
+        let mut options = self.pending.diff_options();
+        for oid in revwalk {
+            if self.pending.is_empty() {
+                break;
+            }
+
+            let commit = repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+
+            let parent = match commit.parent_count() {
+                0 => None,
+                _ => Some(commit.parent(0)?.tree()?),
+            };
+            let diff =
+                repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), Some(&mut options))?;
+            if diff.deltas().len() == 0 {
+                continue;
+            }
+
+            let last = LastCommit {
+                hash: commit
+                    .as_object()
+                    .short_id()?
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                author: String::from_utf8_lossy(commit.author().name_bytes()).into_owned(),
+                time: UNIX_EPOCH + Duration::from_secs(commit.time().seconds().max(0) as u64),
+            };
+
+            let mut narrowed = false;
+            for delta in diff.deltas() {
+                let Some(path) = delta.new_file().path().or(delta.old_file().path()) else {
+                    continue;
+                };
+                let Ok(relative) = path.strip_prefix(prefix) else {
+                    continue;
+                };
+                // Entries deeper than the walk goes are credited to the deepest shown directory.
+                let shown = prefix.join(relative.iter().take(depth).collect::<PathBuf>());
+                narrowed |= self.record(&shown, &last);
+            }
+            if narrowed {
+                options = self.pending.diff_options();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records `commit` for `path` and its ancestors, unless a newer commit
+    /// already touched them. Returns whether the pending frontier moved.
+    #[cfg_attr(not(feature = "git"), allow(dead_code))]
+    fn record(&mut self, path: &Path, commit: &LastCommit) -> bool {
+        let mut narrowed = false;
+        for entry in path.ancestors() {
+            // Ancestors already seen were touched by a newer commit.
+            if self.commits.contains_key(entry) {
+                break;
+            }
+            self.commits.insert(entry.to_path_buf(), commit.clone());
+            narrowed |= self.pending.resolve(entry);
+        }
+        narrowed
+    }
+
+    /// Last commit touching `path`, a path below the walked `root`.
+    pub fn last_commit(&self, path: &Path, root: &Path) -> Option<&LastCommit> {
+        let prefix = self.prefix.as_ref()?;
+        let relative = path.strip_prefix(root).ok()?;
+        self.commits.get(&prefix.join(relative))
+    }
+}
+
+/// Opens the repository containing `dir` from its local `.git`, together
+/// with the location of `dir` relative to the repository root. `None` when
+/// `dir` is not inside a repository with a working tree.
+#[cfg(feature = "git")]
+fn open_repo(dir: &Path) -> Option<(Repository, PathBuf)> {
+    let repo = Repository::discover(dir).ok()?;
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    let prefix = dir
+        .canonicalize()
+        .ok()?
+        .strip_prefix(&workdir)
+        .ok()?
+        .to_path_buf();
+    Some((repo, prefix))
+}
+
+#[cfg(all(feature = "git", unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+#[cfg(all(feature = "git", not(unix)))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(OsStr::new(&*String::from_utf8_lossy(bytes)))
+}
+
+#[cfg(not(feature = "git"))]
+fn without_git(flag: &str) -> anyhow::Error {
+    anyhow::anyhow!("{flag} needs trees-rs built with the `git` feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_from_flags() {
+        use git2::Status;
+
+        let status = GitStatus::from_flags;
+        assert_eq!(status(Status::WT_MODIFIED), GitStatus::Modified);
+        assert_eq!(
+            status(Status::INDEX_MODIFIED | Status::WT_MODIFIED),
+            GitStatus::Modified
+        );
+        assert_eq!(status(Status::INDEX_NEW), GitStatus::Staged);
+        assert_eq!(status(Status::CONFLICTED), GitStatus::Conflicted);
+        assert_eq!(status(Status::WT_NEW), GitStatus::Untracked);
+        assert_eq!(status(Status::IGNORED), GitStatus::Ignored);
+        assert_eq!(status(Status::CURRENT), GitStatus::Clean);
+    }
+
+    #[test]
+    fn test_aggregate_directories() {
+        let mut statuses = GitStatuses {
+            prefix: Some(PathBuf::new()),
+            ..Default::default()
+        };
+        for (path, status) in [
+            ("src/a.rs", GitStatus::Modified),
+            ("src/b.rs", GitStatus::Staged),
+            ("docs/new.md", GitStatus::Untracked),
+            ("src/c.rs", GitStatus::Staged),
+            ("target/", GitStatus::Ignored),
+        ] {
+            statuses.insert(PathBuf::from(path), status);
+        }
+        let root = Path::new("");
+
+        assert_eq!(
+            statuses.status(Path::new("src/a.rs"), root),
+            Some(GitStatus::Modified)
+        );
+        assert_eq!(
+            statuses.status(Path::new("src/c.rs"), root),
+            Some(GitStatus::Staged)
+        );
+        assert_eq!(
+            statuses.status(Path::new("src"), root),
+            Some(GitStatus::Modified)
+        );
+        assert_eq!(
+            statuses.status(Path::new("docs"), root),
+            Some(GitStatus::Untracked)
+        );
+        assert_eq!(
+            statuses.status(Path::new("target/debug/x"), root),
+            Some(GitStatus::Ignored)
+        );
+        assert_eq!(
+            statuses.status(Path::new("README.md"), root),
+            Some(GitStatus::Clean)
+        );
+        assert_eq!(
+            statuses.status(Path::new(""), root),
+            Some(GitStatus::Modified)
+        );
+    }
+
+    #[test]
+    fn test_last_commit() {
+        let mut history = GitHistory {
+            prefix: Some(PathBuf::from("src")),
+            ..Default::default()
+        };
+        let commit = |hash: &str, author: &str, secs| LastCommit {
+            hash: hash.to_string(),
+            author: author.to_string(),
+            time: UNIX_EPOCH + Duration::from_secs(secs),
+        };
+        let newer = commit("b2", "Bob", 200);
+        let older = commit("a1", "Alice", 100);
+        history.record(Path::new("src/a.rs"), &newer);
+        history.record(Path::new("src/a.rs"), &older);
+        history.record(Path::new("src/x/b.rs"), &older);
+        let root = Path::new("");
+
+        let hash = |path| {
+            history
+                .last_commit(Path::new(path), root)
+                .map(|c| c.hash.as_str())
+        };
+        assert_eq!(hash("a.rs"), Some("b2"));
+        assert_eq!(hash("x/b.rs"), Some("a1"));
+        assert_eq!(hash("x"), Some("a1"));
+        assert_eq!(hash(""), Some("b2"));
+        assert_eq!(hash("c.rs"), None);
+
+        let commit = history.last_commit(Path::new("x"), root).unwrap();
+        assert_eq!(commit.author, "Alice");
+        assert_eq!(commit.time, UNIX_EPOCH + Duration::from_secs(100));
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_load_history_to_depth() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+
+        let commit = |message: &str, secs: i64| {
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let author = git2::Signature::new("Alice", "a@x", &git2::Time::new(secs, 0)).unwrap();
+            let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+            let parents: Vec<_> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &author, &author, message, &tree, &parents)
+                .unwrap();
+        };
+
+        std::fs::create_dir_all(root.join("sub/deep")).unwrap();
+        std::fs::write(root.join("[a].txt"), "a").unwrap();
+        std::fs::write(root.join("sub/deep/b.txt"), "b").unwrap();
+        commit("first", 100);
+        std::fs::write(root.join("sub/deep/b.txt"), "changed").unwrap();
+        commit("second", 200);
+
+        let mut history = GitHistory::default();
+        history.load(root, 1).unwrap();
+        let time = |path: &str| history.last_commit(&root.join(path), root).map(|c| c.time);
+
+        assert_eq!(time("[a].txt"), Some(UNIX_EPOCH + Duration::from_secs(100)));
+        assert_eq!(time("sub"), Some(UNIX_EPOCH + Duration::from_secs(200)));
+        assert_eq!(time(""), Some(UNIX_EPOCH + Duration::from_secs(200)));
+        // Deeper entries are not shown and not looked up.
+        assert_eq!(time("sub/deep"), None);
+        assert!(history.pending.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::Metadata;
+
+/// Remembers where each multiply-linked inode was first printed, so later
+/// hard links to it can point back to that path.
+#[derive(Debug, Default, Clone)]
+pub struct HardLinks {
+    enabled: bool,
+    seen: HashMap<(u64, u64), OsString>,
+}
+
+impl HardLinks {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records `path` as the first occurrence of the inode behind `meta`.
+    /// The path is only built for inodes with more than one link.
+    ///
+    /// Returns the path of the first occurrence if this inode was already printed.
+    #[cfg(unix)]
+    pub fn first_seen(
+        &mut self,
+        meta: &Metadata,
+        path: impl FnOnce() -> OsString,
+    ) -> Option<OsString> {
+        use std::os::unix::fs::MetadataExt;
+
+        if !self.enabled || meta.is_dir() || meta.nlink() < 2 {
+            return None;
+        }
+
+        match self.seen.get(&(meta.dev(), meta.ino())) {
+            Some(first) => Some(first.clone()),
+            None => {
+                self.seen.insert((meta.dev(), meta.ino()), path());
+                None
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn first_seen(
+        &mut self,
+        _meta: &Metadata,
+        _path: impl FnOnce() -> OsString,
+    ) -> Option<OsString> {
+        None
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_first_seen() {
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let original = temp_dir.path().join("original");
+        let linked = temp_dir.path().join("linked");
+        File::create(&original).expect("Failed to create file");
+        fs::hard_link(&original, &linked).expect("Failed to create hard link");
+
+        let mut links = HardLinks::default();
+        let meta = fs::metadata(&original).unwrap();
+        assert_eq!(links.first_seen(&meta, || "original".into()), None);
+
+        links.enable();
+        assert_eq!(links.first_seen(&meta, || "original".into()), None);
+
+        let meta = fs::metadata(&linked).unwrap();
+        assert_eq!(
+            links.first_seen(&meta, || "linked".into()),
+            Some(OsString::from("original"))
+        );
+    }
+}
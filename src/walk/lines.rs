@@ -0,0 +1,217 @@
+use super::follow::Follow;
+use crate::config::registry::Registry;
+use crate::report::stats::DirectoryStats;
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::fs::File;
+use std::fs::Metadata;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Leading bytes searched for a NUL to tell binary files apart from text.
+const BINARY_PROBE_LEN: usize = 8 * 1024;
+
+/// Counts lines of text files and rolls them up per directory.
+///
+/// A directory is printed before its children, so its total is computed
+/// up front and the counts of everything below it are kept until the walk
+/// reaches them. Only the levels the walk will print are counted, so the
+/// totals agree with `--code-stats`.
+#[derive(Debug, Default)]
+pub struct LineCounter {
+    enabled: bool,
+    /// Counts not printed yet, keyed by parent directory and then by name.
+    pending: HashMap<PathBuf, HashMap<OsString, Option<u64>>>,
+}
+
+impl LineCounter {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Lines of the text file at `path`, or of all text files up to `depth`
+    /// levels below the directory at `path`. `None` for binary files and
+    /// special entries. Symlinked directories count where `follow` would
+    /// descend into them.
+    pub fn lines(
+        &mut self,
+        path: &Path,
+        meta: &Metadata,
+        rg: &Registry,
+        follow: &Follow,
+        depth: i32,
+    ) -> Option<u64> {
+        if !self.enabled {
+            return None;
+        }
+
+        let pending = path
+            .parent()
+            .zip(path.file_name())
+            .and_then(|(dir, name)| self.pending.get_mut(dir)?.remove(name));
+
+        match pending {
+            Some(lines) => lines,
+            None => self.count(path, meta, rg, &mut follow.clone(), depth),
+        }
+    }
+
+    /// Forget the counts below `dir` once it has been walked, including the
+    /// ones of entries the walk skipped.
+    pub fn leave(&mut self, dir: &Path) {
+        self.pending.remove(dir);
+    }
+
+    fn count(
+        &mut self,
+        path: &Path,
+        meta: &Metadata,
+        rg: &Registry,
+        follow: &mut Follow,
+        depth: i32,
+    ) -> Option<u64> {
+        if meta.is_file() {
+            count_lines(path).ok().flatten()
+        } else if meta.is_dir() {
+            Some(self.rollup(path, rg, follow, depth))
+        } else if meta.is_symlink() && follow.is_enabled() {
+            // Same metadata the walk reads before descending into the link.
+            match fs::metadata(path) {
+                Ok(target) if target.is_dir() && !follow.loops(&target) => {
+                    Some(self.rollup(path, rg, follow, depth))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    fn rollup(&mut self, dir: &Path, rg: &Registry, follow: &mut Follow, depth: i32) -> u64 {
+        if depth <= 0 {
+            return 0;
+        }
+
+        // Hidden entries are already counted by the walk itself.
+        let mut scratch = DirectoryStats::default();
+        let Ok(entries) = rg.inspt_dents(dir.to_path_buf(), &mut scratch) else {
+            return 0;
+        };
+
+        let mut total = 0;
+        let mut children = HashMap::with_capacity(entries.len());
+        follow.enter(dir);
+        for entry in entries {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            let lines = self.count(&entry.path(), &meta, rg, follow, depth - 1);
+            total += lines.unwrap_or(0);
+            children.insert(entry.file_name(), lines);
+        }
+        follow.leave();
+        self.pending.insert(dir.to_path_buf(), children);
+        total
+    }
+}
+
+/// Returns `None` if the file looks binary.
+pub fn count_lines(path: &Path) -> io::Result<Option<u64>> {
+    count_lines_in(File::open(path)?)
+}
+
+fn count_lines_in<R: Read>(mut reader: R) -> io::Result<Option<u64>> {
+    let mut chunk = vec![0; 64 * 1024];
+    let mut lines = 0;
+    let mut probed = 0;
+    let mut last = b'\n';
+
+    loop {
+        let n = reader.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+
+        if probed < BINARY_PROBE_LEN {
+            let end = n.min(BINARY_PROBE_LEN - probed);
+            if chunk[..end].contains(&0) {
+                return Ok(None);
+            }
+            probed += end;
+        }
+
+        lines += chunk[..n].iter().filter(|&&b| b == b'\n').count() as u64;
+        last = chunk[n - 1];
+    }
+
+    // Count a trailing line without a newline.
+    if last != b'\n' {
+        lines += 1;
+    }
+
+    Ok(Some(lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines_in(b"".as_slice()).unwrap(), Some(0));
+        assert_eq!(count_lines_in(b"one\ntwo\n".as_slice()).unwrap(), Some(2));
+        assert_eq!(count_lines_in(b"one\ntwo".as_slice()).unwrap(), Some(2));
+        assert_eq!(count_lines_in(b"\x7fELF\x00\x01".as_slice()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rollup_depth() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("sub")).unwrap();
+        std::fs::write(root.join("top.txt"), "one\n").unwrap();
+        std::fs::write(root.join("sub").join("deep.txt"), "one\ntwo\n").unwrap();
+
+        let rg = Registry::new().unwrap();
+        let meta = std::fs::metadata(root).unwrap();
+        let mut counter = LineCounter::default();
+        counter.enable();
+
+        let follow = Follow::default();
+        assert_eq!(counter.lines(root, &meta, &rg, &follow, 1), Some(1));
+        counter.leave(root);
+        assert!(counter.pending.is_empty());
+
+        assert_eq!(counter.lines(root, &meta, &rg, &follow, 2), Some(3));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rollup_follows_symlinks() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let root = temp_dir.path().join("root");
+        let other = temp_dir.path().join("other");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::create_dir(&other).unwrap();
+        std::fs::write(other.join("a.txt"), "one\ntwo\n").unwrap();
+        std::os::unix::fs::symlink(&other, root.join("link")).unwrap();
+        std::os::unix::fs::symlink(&root, root.join("up")).unwrap();
+
+        let rg = Registry::new().unwrap();
+        let meta = std::fs::metadata(&root).unwrap();
+        let mut counter = LineCounter::default();
+        counter.enable();
+
+        let mut follow = Follow::default();
+        assert_eq!(counter.lines(&root, &meta, &rg, &follow, 5), Some(0));
+        counter.leave(&root);
+
+        // `up` loops back to the root and is skipped like the walk does
+        follow.enable();
+        assert_eq!(counter.lines(&root, &meta, &rg, &follow, 5), Some(2));
+    }
+}
@@ -0,0 +1,329 @@
+use super::visit::Visitor;
+use crate::config::category::Categories;
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+
+/// Number of leading bytes inspected, enough to reach the tar header magic.
+const SNIFF_LEN: usize = 512;
+
+pub type FnClassify = fn(&mut Visitor, &Categories);
+
+/// Put regular files into a category by their extension.
+pub fn classify_by_extension(visitor: &mut Visitor, categories: &Categories) {
+    if !visitor.is_file() {
+        return;
+    }
+
+    let category = visitor
+        .absolute_path()
+        .and_then(|path| categories.by_path(path));
+
+    visitor.with_category(category);
+}
+
+/// Put regular files into a category by their leading bytes, falling back
+/// to the extension for formats without a signature such as source code.
+pub fn classify_by_content(visitor: &mut Visitor, categories: &Categories) {
+    if !visitor.is_file() {
+        return;
+    }
+
+    let kind = visitor
+        .absolute_path()
+        .and_then(|path| sniff(path).ok())
+        .flatten();
+
+    let category = kind
+        .and_then(|kind| categories.by_name(kind.category()))
+        .or_else(|| {
+            visitor
+                .absolute_path()
+                .and_then(|path| categories.by_path(path))
+        });
+
+    visitor.with_kind(kind);
+    visitor.with_category(category);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileKind {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Bmp,
+    Tiff,
+    Wav,
+    Flac,
+    Ogg,
+    Mp3,
+    Avi,
+    Mp4,
+    Matroska,
+    Pdf,
+    Sqlite,
+    Elf,
+    MachO,
+    Pe,
+    Wasm,
+    Script,
+    Gzip,
+    Zip,
+    Xz,
+    Bzip2,
+    Zstd,
+    SevenZip,
+    Rar,
+    Tar,
+}
+
+impl FileKind {
+    /// Widest label, used to keep the type column aligned.
+    pub const LABEL_WIDTH: usize = 8;
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FileKind::Png => "png",
+            FileKind::Jpeg => "jpeg",
+            FileKind::Gif => "gif",
+            FileKind::Webp => "webp",
+            FileKind::Bmp => "bmp",
+            FileKind::Tiff => "tiff",
+            FileKind::Wav => "wav",
+            FileKind::Flac => "flac",
+            FileKind::Ogg => "ogg",
+            FileKind::Mp3 => "mp3",
+            FileKind::Avi => "avi",
+            FileKind::Mp4 => "mp4",
+            FileKind::Matroska => "matroska",
+            FileKind::Pdf => "pdf",
+            FileKind::Sqlite => "sqlite",
+            FileKind::Elf => "elf",
+            FileKind::MachO => "mach-o",
+            FileKind::Pe => "pe",
+            FileKind::Wasm => "wasm",
+            FileKind::Script => "script",
+            FileKind::Gzip => "gzip",
+            FileKind::Zip => "zip",
+            FileKind::Xz => "xz",
+            FileKind::Bzip2 => "bzip2",
+            FileKind::Zstd => "zstd",
+            FileKind::SevenZip => "7z",
+            FileKind::Rar => "rar",
+            FileKind::Tar => "tar",
+        }
+    }
+
+    /// Name of the built-in category this format belongs to.
+    pub fn category(&self) -> &'static str {
+        match self {
+            FileKind::Png
+            | FileKind::Jpeg
+            | FileKind::Gif
+            | FileKind::Webp
+            | FileKind::Bmp
+            | FileKind::Tiff => "image",
+            FileKind::Wav | FileKind::Flac | FileKind::Ogg | FileKind::Mp3 => "audio",
+            FileKind::Avi | FileKind::Mp4 | FileKind::Matroska => "video",
+            FileKind::Pdf | FileKind::Sqlite => "document",
+            FileKind::Elf | FileKind::MachO | FileKind::Pe | FileKind::Wasm | FileKind::Script => {
+                "executable"
+            }
+            FileKind::Gzip
+            | FileKind::Zip
+            | FileKind::Xz
+            | FileKind::Bzip2
+            | FileKind::Zstd
+            | FileKind::SevenZip
+            | FileKind::Rar
+            | FileKind::Tar => "archive",
+        }
+    }
+}
+
+/// Reads the beginning of the file at `path` and identifies its format.
+pub fn sniff(path: &Path) -> io::Result<Option<FileKind>> {
+    let mut header = Vec::with_capacity(SNIFF_LEN);
+    File::open(path)?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut header)?;
+    Ok(detect(&header))
+}
+
+/// Identifies a format from its magic bytes.
+pub fn detect(header: &[u8]) -> Option<FileKind> {
+    #[rustfmt::skip]
+    static SIGNATURES: &[(&[u8], FileKind)] = &[
+        (b"\x89PNG\r\n\x1a\n",      FileKind::Png),
+        (b"\xff\xd8\xff",           FileKind::Jpeg),
+        (b"GIF87a",                 FileKind::Gif),
+        (b"GIF89a",                 FileKind::Gif),
+        (b"II*\x00",                FileKind::Tiff),
+        (b"MM\x00*",                FileKind::Tiff),
+        (b"fLaC",                   FileKind::Flac),
+        (b"OggS",                   FileKind::Ogg),
+        (b"\x1a\x45\xdf\xa3",       FileKind::Matroska),
+        (b"%PDF-",                  FileKind::Pdf),
+        (b"SQLite format 3\x00",    FileKind::Sqlite),
+        (b"\x7fELF",                FileKind::Elf),
+        (b"\xfe\xed\xfa\xce",       FileKind::MachO),
+        (b"\xfe\xed\xfa\xcf",       FileKind::MachO),
+        (b"\xce\xfa\xed\xfe",       FileKind::MachO),
+        (b"\xcf\xfa\xed\xfe",       FileKind::MachO),
+        (b"\x00asm",                FileKind::Wasm),
+        (b"#!",                     FileKind::Script),
+        (b"\x1f\x8b",               FileKind::Gzip),
+        (b"PK\x03\x04",             FileKind::Zip),
+        (b"PK\x05\x06",             FileKind::Zip),
+        (b"\xfd7zXZ\x00",           FileKind::Xz),
+        (b"\x28\xb5\x2f\xfd",       FileKind::Zstd),
+        (b"7z\xbc\xaf\x27\x1c",     FileKind::SevenZip),
+        (b"Rar!\x1a\x07",           FileKind::Rar),
+    ];
+
+    if let Some((_, kind)) = SIGNATURES
+        .iter()
+        .find(|(magic, _)| header.starts_with(magic))
+    {
+        return Some(*kind);
+    }
+
+    // RIFF containers carry their format after the chunk size.
+    if header.starts_with(b"RIFF") && header.len() >= 12 {
+        match &header[8..12] {
+            b"WEBP" => return Some(FileKind::Webp),
+            b"WAVE" => return Some(FileKind::Wav),
+            b"AVI " => return Some(FileKind::Avi),
+            _ => {}
+        }
+    }
+
+    // ISO base media files (mp4, mov, m4a) start with an `ftyp` box.
+    if header.len() >= 8 && &header[4..8] == b"ftyp" {
+        return Some(FileKind::Mp4);
+    }
+
+    if header.len() >= 263 && &header[257..262] == b"ustar" {
+        return Some(FileKind::Tar);
+    }
+
+    // Short signatures also turn up at the start of text files, so they
+    // only count along with the header fields that follow them.
+    if is_pe(header) {
+        return Some(FileKind::Pe);
+    }
+    if is_bzip2(header) {
+        return Some(FileKind::Bzip2);
+    }
+    if is_bmp(header) {
+        return Some(FileKind::Bmp);
+    }
+    if is_id3(header) {
+        return Some(FileKind::Mp3);
+    }
+
+    None
+}
+
+/// `MZ` stub whose `e_lfanew` field points at a `PE\0\0` header.
+fn is_pe(header: &[u8]) -> bool {
+    header.starts_with(b"MZ")
+        && u32_le(header, 0x3c)
+            .and_then(|offset| header.get(offset as usize..))
+            .is_some_and(|pe| pe.starts_with(b"PE\0\0"))
+}
+
+/// `BZh`, the block size digit, then the magic of a block or of the stream end.
+fn is_bzip2(header: &[u8]) -> bool {
+    header.starts_with(b"BZh")
+        && header
+            .get(3)
+            .is_some_and(|digit| (b'1'..=b'9').contains(digit))
+        && header.get(4..10).is_some_and(|magic| {
+            magic == b"\x31\x41\x59\x26\x53\x59" || magic == b"\x17\x72\x45\x38\x50\x90"
+        })
+}
+
+/// `BM`, zeroed reserved fields and the size of a known DIB header.
+fn is_bmp(header: &[u8]) -> bool {
+    header.starts_with(b"BM")
+        && header.get(6..10).is_some_and(|reserved| reserved == [0; 4])
+        && u32_le(header, 14).is_some_and(|size| matches!(size, 12 | 40 | 52 | 56 | 64 | 108 | 124))
+}
+
+/// `ID3`, a major version from 2 to 4 and a synchsafe tag size.
+fn is_id3(header: &[u8]) -> bool {
+    header.starts_with(b"ID3")
+        && header.get(3).is_some_and(|major| (2..=4).contains(major))
+        && header.get(4).is_some_and(|revision| *revision != 0xff)
+        && header
+            .get(6..10)
+            .is_some_and(|size| size.iter().all(|byte| *byte < 0x80))
+}
+
+fn u32_le(header: &[u8], offset: usize) -> Option<u32> {
+    let bytes = header.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_signatures() {
+        assert_eq!(detect(b"\x89PNG\r\n\x1a\n...."), Some(FileKind::Png));
+        assert_eq!(detect(b"\x7fELF\x02\x01\x01"), Some(FileKind::Elf));
+        assert_eq!(detect(b"#!/bin/sh\necho hi"), Some(FileKind::Script));
+        assert_eq!(detect(b"PK\x03\x04\x14\x00"), Some(FileKind::Zip));
+        assert_eq!(detect(b"%PDF-1.7"), Some(FileKind::Pdf));
+        assert_eq!(detect(b"\x1f\x8b\x08\x00"), Some(FileKind::Gzip));
+        assert_eq!(detect(b"RIFF\x24\x00\x00\x00WAVEfmt "), Some(FileKind::Wav));
+        assert_eq!(detect(b"\x00\x00\x00\x20ftypisom"), Some(FileKind::Mp4));
+    }
+
+    #[test]
+    fn test_detect_tar_and_plain_text() {
+        let mut tar = vec![0; 512];
+        tar[257..263].copy_from_slice(b"ustar\x00");
+        assert_eq!(detect(&tar), Some(FileKind::Tar));
+
+        assert_eq!(detect(b"fn main() {}\n"), None);
+        assert_eq!(detect(b""), None);
+    }
+
+    #[test]
+    fn test_detect_short_signatures() {
+        let mut pe = vec![0; 0x84];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        pe[0x80..].copy_from_slice(b"PE\0\0");
+        assert_eq!(detect(&pe), Some(FileKind::Pe));
+
+        let mut bmp = vec![0; 18];
+        bmp[..2].copy_from_slice(b"BM");
+        bmp[14..18].copy_from_slice(&40u32.to_le_bytes());
+        assert_eq!(detect(&bmp), Some(FileKind::Bmp));
+
+        assert_eq!(detect(b"BZh91AY&SY\x00"), Some(FileKind::Bzip2));
+        assert_eq!(
+            detect(b"ID3\x04\x00\x00\x00\x00\x1f\x76"),
+            Some(FileKind::Mp3)
+        );
+
+        assert_eq!(detect(b"MZ is the start of this note\n"), None);
+        assert_eq!(detect(b"BM: build manifest, version 2\n"), None);
+        assert_eq!(detect(b"BZh, not compressed\n"), None);
+        assert_eq!(detect(b"ID3 tags are described below\n"), None);
+    }
+
+    #[test]
+    fn test_category() {
+        assert_eq!(FileKind::Png.category(), "image");
+        assert_eq!(FileKind::Script.category(), "executable");
+        assert_eq!(FileKind::Tar.category(), "archive");
+    }
+}
@@ -1,2 +1,11 @@
+pub mod digest;
+pub mod follow;
+pub mod git;
+pub mod hardlink;
+pub mod lines;
+pub mod magic;
+pub mod mount;
 pub mod tr;
+pub mod verify;
 pub mod visit;
+pub mod xattr;
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::Metadata;
+use std::path::Path;
+use std::path::PathBuf;
+
+static MOUNTINFO: &str = "/proc/self/mountinfo";
+
+/// Keeps a `--one-file-system` walk on the device the root lives on.
+#[derive(Debug, Default, Clone)]
+pub struct OneFileSystem {
+    enabled: bool,
+    /// Device ID of the root, set once the root has been read.
+    root: Option<u64>,
+}
+
+impl OneFileSystem {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Remembers the device of the walked `root`, left unknown if it cannot be read.
+    pub fn with_root(&mut self, root: &Path) {
+        self.root = device_of(root);
+    }
+
+    /// Whether the directory behind `meta` is on another filesystem than the root.
+    pub fn crosses(&self, meta: &Metadata) -> bool {
+        self.enabled && self.root.is_some() && device_id(meta) != self.root
+    }
+}
+
+/// A filesystem mounted on a directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mount {
+    pub fstype: String,
+    pub source: String,
+}
+
+impl Mount {
+    /// `tmpfs`, or `nfs4 server:/x` when the source says more than the type.
+    pub fn label(&self) -> String {
+        if self.source == self.fstype || self.source == "none" || self.source.is_empty() {
+            self.fstype.clone()
+        } else {
+            format!("{} {}", self.fstype, self.source)
+        }
+    }
+}
+
+/// Mount points of the running system, keyed by their path.
+#[derive(Debug, Default, Clone)]
+pub struct MountTable {
+    mounts: HashMap<PathBuf, Mount>,
+    /// Canonical paths of the directories being walked, innermost last.
+    dirs: Vec<Option<PathBuf>>,
+}
+
+impl MountTable {
+    /// Reads `/proc/self/mountinfo`, leaving the table empty where it does not exist.
+    pub fn load() -> Self {
+        std::fs::read_to_string(MOUNTINFO)
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    /// Parses mountinfo lines such as
+    /// `36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw`.
+    ///
+    /// Later lines are mounted on top of earlier ones at the same path, so they win.
+    pub fn parse(text: &str) -> Self {
+        let mut mounts = HashMap::new();
+
+        for line in text.lines() {
+            let mut fields = line.split(' ');
+            let Some(mount_point) = fields.nth(4) else {
+                continue;
+            };
+            // Optional fields end with a lone `-`
+            let mut fields = fields.skip_while(|field| *field != "-").skip(1);
+            let (Some(fstype), Some(source)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+
+            mounts.insert(
+                PathBuf::from(unescape(mount_point)),
+                Mount {
+                    fstype: unescape(fstype),
+                    source: unescape(source),
+                },
+            );
+        }
+
+        MountTable {
+            mounts,
+            dirs: Vec::new(),
+        }
+    }
+
+    /// The filesystem mounted on `path`, if it is a mount point.
+    ///
+    /// The path is canonicalized first, so symlinks and `..` reach the mount point too.
+    pub fn get(&self, path: &Path) -> Option<&Mount> {
+        if self.mounts.is_empty() {
+            return None;
+        }
+
+        let path = std::fs::canonicalize(path).ok()?;
+        self.mounted_on(&path)
+    }
+
+    /// Starts walking the directory at `path`.
+    ///
+    /// Only the root and followed symlinks are canonicalized, other directories
+    /// extend the canonical path of their parent by their name.
+    pub fn enter(&mut self, path: &Path) {
+        if self.mounts.is_empty() {
+            return;
+        }
+
+        let dir = match (self.dirs.last(), path.file_name()) {
+            (Some(Some(parent)), Some(name)) if !path.is_symlink() => Some(parent.join(name)),
+            _ => std::fs::canonicalize(path).ok(),
+        };
+        self.dirs.push(dir);
+    }
+
+    pub fn leave(&mut self) {
+        self.dirs.pop();
+    }
+
+    /// The filesystem mounted on the directory `name` inside the one being walked.
+    pub fn get_child(&self, name: &OsStr) -> Option<&Mount> {
+        let parent = self.dirs.last()?.as_ref()?;
+        self.mounted_on(&parent.join(name))
+    }
+
+    /// The filesystem mounted on the already canonical `path`.
+    fn mounted_on(&self, path: &Path) -> Option<&Mount> {
+        self.mounts.get(path)
+    }
+}
+
+/// Undoes the octal escapes (`\040` for a space) the kernel applies to mountinfo fields.
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        let octal = bytes.get(idx + 1..idx + 4).filter(|digits| {
+            bytes[idx] == b'\\' && digits.iter().all(|digit| (b'0'..=b'7').contains(digit))
+        });
+        match octal {
+            Some(digits) => {
+                let value = digits
+                    .iter()
+                    .fold(0u32, |acc, digit| acc * 8 + u32::from(digit - b'0'));
+                unescaped.push(value as u8);
+                idx += 4;
+            }
+            None => {
+                unescaped.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// Device ID of the entry behind `path`, following symlinks.
+fn device_of(path: &Path) -> Option<u64> {
+    std::fs::metadata(path).ok().as_ref().and_then(device_id)
+}
+
+#[cfg(unix)]
+fn device_id(meta: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(meta.dev())
+}
+
+#[cfg(not(unix))]
+fn device_id(_meta: &Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_crosses() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let meta = std::fs::metadata(temp_dir.path()).unwrap();
+
+        let mut one_fs = OneFileSystem::default();
+        one_fs.with_root(temp_dir.path());
+        assert!(!one_fs.crosses(&meta));
+
+        one_fs.enable();
+        assert!(!one_fs.crosses(&meta));
+
+        one_fs.with_root(&temp_dir.path().join("missing"));
+        assert!(!one_fs.crosses(&meta));
+
+        one_fs.root = device_id(&meta).map(|dev| dev + 1);
+        assert!(one_fs.crosses(&meta));
+    }
+
+    #[test]
+    fn test_parse_mountinfo() {
+        let table = MountTable::parse(
+            "22 1 0:21 / / rw - overlay overlay rw,lowerdir=/l\n\
+             30 22 0:26 / /dev/shm rw,relatime shared:5 - tmpfs tmpfs rw\n\
+             31 22 0:40 /x /mnt/my\\040share rw master:1 - nfs4 server:/x rw\n\
+             32 30 0:27 / /dev/shm rw - ramfs none rw\n",
+        );
+
+        let label = |path: &str| table.mounted_on(Path::new(path)).map(Mount::label);
+        assert_eq!(label("/"), Some("overlay".to_string()));
+        assert_eq!(label("/dev/shm"), Some("ramfs".to_string()));
+        assert_eq!(label("/mnt/my share"), Some("nfs4 server:/x".to_string()));
+        assert_eq!(label("/mnt"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_resolves_symlinks() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let link = temp_dir.path().join("root");
+        std::os::unix::fs::symlink("/", &link).expect("Failed to create symlink");
+
+        let table = MountTable::parse("22 1 0:21 / / rw - overlay overlay rw\n");
+        assert_eq!(
+            table.get(&link).map(Mount::label),
+            Some("overlay".to_string())
+        );
+        assert_eq!(
+            table.get(&link.join("..")).map(Mount::label),
+            Some("overlay".to_string())
+        );
+        assert_eq!(table.get(temp_dir.path()).map(Mount::label), None);
+        assert_eq!(MountTable::default().get(&link), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_child() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let root = std::fs::canonicalize(temp_dir.path()).unwrap();
+        std::fs::create_dir_all(root.join("a/bind")).unwrap();
+        std::fs::create_dir(root.join("b")).unwrap();
+        std::os::unix::fs::symlink(root.join("a"), root.join("b/link")).unwrap();
+
+        // A bind mount keeps the device of its parent, only its path marks it.
+        let escaped = root.to_str().unwrap().replace(' ', "\\040");
+        let mut table = MountTable::parse(&format!(
+            "40 22 8:1 /src {escaped}/a/bind rw - ext4 /dev/sda1 rw\n"
+        ));
+        let label =
+            |table: &MountTable, name: &str| table.get_child(OsStr::new(name)).map(Mount::label);
+
+        table.enter(temp_dir.path());
+        assert_eq!(label(&table, "a"), None);
+        table.enter(&temp_dir.path().join("a"));
+        assert_eq!(label(&table, "bind"), Some("ext4 /dev/sda1".to_string()));
+        table.leave();
+
+        table.enter(&temp_dir.path().join("b"));
+        table.enter(&temp_dir.path().join("b/link"));
+        assert_eq!(label(&table, "bind"), Some("ext4 /dev/sda1".to_string()));
+        table.leave();
+        table.leave();
+        table.leave();
+        assert_eq!(label(&table, "a"), None);
+    }
+}
@@ -12,6 +12,22 @@ pub struct TreeCtxt<'tr, 'a> {
     pub rg: config::registry::Registry<'tr>,
     pub dir_stats: report::stats::DirectoryStats,
     pub path_builder: config::root::PathBuilder,
+    pub accounts: config::accounts::Accounts,
+    pub hardlinks: walk::hardlink::HardLinks,
+    pub timefmt: config::timefmt::TimeFormat,
+    pub digest: walk::digest::Digest,
+    pub verifier: walk::verify::Verifier,
+    pub categories: config::category::Categories,
+    pub line_counter: walk::lines::LineCounter,
+    pub code_stats: report::code::CodeStats,
+    pub git: walk::git::GitStatuses,
+    pub git_history: walk::git::GitHistory,
+    pub acl: walk::xattr::Acl,
+    pub ls_colors: config::lscolors::LsColors,
+    pub theme: config::theme::Theme,
+    pub follow: walk::follow::Follow,
+    pub one_fs: walk::mount::OneFileSystem,
+    pub mounts: walk::mount::MountTable,
 }
 
 impl<'tr, 'a> TreeCtxt<'tr, 'a> {
@@ -20,11 +36,26 @@ impl<'tr, 'a> TreeCtxt<'tr, 'a> {
     ) -> anyhow::Result<Self> {
         let branch = tree::branch::Branch::default();
         let nod = tree::node::Node::default();
-        let dir_stats = report::stats::DirectoryStats::default();
+        let categories = config::category::Categories::default();
+        let mut dir_stats = report::stats::DirectoryStats::default();
+        dir_stats.with_categories(&categories);
         let level = tree::level::Level::default();
         let rg = config::registry::Registry::new()?;
         let path_builder = config::root::PathBuilder::default();
-
+        let accounts = config::accounts::Accounts::default();
+        let hardlinks = walk::hardlink::HardLinks::default();
+        let timefmt = config::timefmt::TimeFormat::default();
+        let digest = walk::digest::Digest::default();
+        let verifier = walk::verify::Verifier::default();
+        let line_counter = walk::lines::LineCounter::default();
+        let code_stats = report::code::CodeStats::default();
+        let git = walk::git::GitStatuses::default();
+        let git_history = walk::git::GitHistory::default();
+        let acl = walk::xattr::Acl::default();
+        let ls_colors = config::lscolors::LsColors::from_env();
+        let theme = config::theme::Theme::default();
+        let follow = walk::follow::Follow::default();
+        let one_fs = walk::mount::OneFileSystem::default();
         Ok(Self {
             branch,
             buf,
@@ -33,10 +64,47 @@ impl<'tr, 'a> TreeCtxt<'tr, 'a> {
             rg,
             dir_stats,
             path_builder,
+            accounts,
+            hardlinks,
+            timefmt,
+            digest,
+            verifier,
+            categories,
+            line_counter,
+            code_stats,
+            git,
+            git_history,
+            acl,
+            ls_colors,
+            theme,
+            follow,
+            one_fs,
+            mounts: walk::mount::MountTable::default(),
         })
     }
 
+    pub fn with_categories(&mut self, categories: config::category::Categories) {
+        self.dir_stats.with_categories(&categories);
+        self.categories = categories;
+    }
+
     pub fn walk_dir(&mut self, path: std::path::PathBuf) -> anyhow::Result<()> {
+        self.follow.enter(&path);
+        self.mounts.enter(&path);
+        let walked = self.walk_entries(path.clone());
+        self.mounts.leave();
+        self.follow.leave();
+        self.line_counter.leave(&path);
+        walked
+    }
+
+    fn walk_entries(&mut self, path: std::path::PathBuf) -> anyhow::Result<()> {
+        // Manifest entries that vanished from this directory
+        let missing = match path.strip_prefix(self.path_builder.base_path()) {
+            Ok(relative_dir) => self.verifier.missing_in(&path, relative_dir),
+            Err(_) => Vec::new(),
+        };
+
         // Get entries in target path
         let mut entries: Vec<std::fs::DirEntry> = self.rg.inspt_dents(path, &mut self.dir_stats)?;
 
@@ -45,58 +113,120 @@ impl<'tr, 'a> TreeCtxt<'tr, 'a> {
         let enumerated_entries: Vec<(usize, std::fs::DirEntry)> =
             entries.into_iter().enumerate().collect();
 
-        let entries_len = enumerated_entries.len();
+        let present_len = enumerated_entries.len();
+        let entries_len = present_len + missing.len();
 
         for (idx, entry) in enumerated_entries {
             // Get entry's information
             let mut visitor = walk::visit::Visitor::new(entry)?;
+            self.rg.classify(&mut visitor, &self.categories);
             // Accumulate entry's size
             self.dir_stats.add_size(visitor.size().unwrap());
+            self.dir_stats.add_disk_usage(visitor.disk_usage().unwrap());
             // Print entry's information
-            self.print_info(visitor.metadata())?;
+            self.print_info(
+                visitor.metadata(),
+                visitor.absolute_path().unwrap(),
+                visitor.kind(),
+            )?;
             // If current entry is not the last entry in entries
             self.nod.push_if(idx, entries_len);
             // Convert node to branch's stick
             self.nod.to_branch(&self.branch, self.buf)?;
+            self.buf
+                .print_icon(visitor.filename(), visitor.metadata(), self.rg.icon)?;
 
             if visitor.is_symlink() {
                 self.dir_stats.symlink_add_one();
-                self.rg.yellow(self.buf)?;
+                let target = visitor.target_metadata();
+                let dangling = target.as_ref().is_err_and(walk::visit::is_dangling);
+                let target = target.ok();
+                if dangling {
+                    self.dir_stats.broken_symlink_add_one();
+                    if !self.paint_theme(|theme| theme.entry("orphan"))?
+                        && !self.paint_ls_colors(&visitor)?
+                    {
+                        self.rg.orphan(self.buf)?;
+                    }
+                } else if !self.paint_git_entry(&visitor)?
+                    && !self.paint_theme(|theme| theme.entry("symlink"))?
+                    && !self.paint_ls_colors(&visitor)?
+                {
+                    self.rg.yellow(self.buf)?;
+                }
                 self.buf
                     .print_symlink(&mut visitor, &self.path_builder, self.rg.symlink)?;
                 self.rg.reset(self.buf)?;
 
                 self.buf.write_message(" @ ")?;
 
-                self.rg.underlined_blue(self.buf)?;
-                self.buf.write_message(
+                if dangling {
+                    if !self.paint_theme(|theme| theme.entry("orphan"))? {
+                        self.rg.orphan(self.buf)?;
+                    }
+                } else if !self.paint_theme(|theme| theme.entry("target"))? {
+                    self.rg.underlined_blue(self.buf)?;
+                }
+                self.buf.write_os_string(
                     visitor
                         .get_target_symlink()
                         .expect("Cannot get target link.")
-                        .to_str()
-                        .expect("Cannot convert target symlink to &str"),
+                        .into_os_string(),
                 )?;
                 self.rg.reset(self.buf)?;
-                self.buf.newline()?;
-                self.nod.pop();
-                continue;
-            }
+                // Like `tree -F`, classify what the link points to.
+                if let Some(meta) = &target {
+                    self.buf.print_indicator(meta, self.rg.indicator)?;
+                }
 
-            if visitor.is_media_type() {
-                self.dir_stats.media_add_one();
-                self.rg.purple(self.buf)?;
-                self.buf
-                    .print_file(&visitor, &self.path_builder, self.rg.file)?;
-                self.rg.reset(self.buf)?;
-                self.buf.newline()?;
+                match target.filter(|meta| self.follow.is_enabled() && meta.is_dir()) {
+                    Some(meta) if self.follow.loops(&meta) => {
+                        self.buf.write_message(" [recursive, not followed]")?;
+                        self.buf.newline()?;
+                    }
+                    Some(meta) if self.one_fs.crosses(&meta) => {
+                        self.buf
+                            .write_message(" [other filesystem, not followed]")?;
+                        self.buf.newline()?;
+                    }
+                    Some(_) => {
+                        self.buf.newline()?;
+                        self.descend(visitor.absolute_path().unwrap().clone());
+                    }
+                    None => self.buf.newline()?,
+                }
                 self.nod.pop();
                 continue;
             }
 
             if visitor.is_file() {
                 self.dir_stats.file_add_one();
+                if let Some(category) = visitor.category() {
+                    self.dir_stats
+                        .category_add_one(category, visitor.size().unwrap());
+                }
+                let status = self.verify_status(&visitor);
+                let painted = match status {
+                    Some(status) => {
+                        self.paint_status(status)?;
+                        true
+                    }
+                    None => {
+                        self.paint_git_entry(&visitor)?
+                            || self.paint_theme_category(&visitor)?
+                            || self.paint_ls_colors(&visitor)?
+                            || self.paint_builtin_category(&visitor)?
+                    }
+                };
                 self.buf
                     .print_file(&visitor, &self.path_builder, self.rg.file)?;
+                if painted {
+                    self.rg.reset(self.buf)?;
+                }
+                self.buf
+                    .print_indicator(visitor.metadata(), self.rg.indicator)?;
+                self.print_status(status)?;
+                self.print_hardlink(&visitor)?;
                 self.buf.newline()?;
                 self.nod.pop();
                 continue;
@@ -104,26 +234,28 @@ impl<'tr, 'a> TreeCtxt<'tr, 'a> {
 
             if visitor.is_dir() {
                 self.dir_stats.dir_add_one();
-                self.rg.blue(self.buf)?;
+                if !self.paint_git_entry(&visitor)?
+                    && !self.paint_theme(|theme| theme.entry("directory"))?
+                    && !self.paint_ls_colors(&visitor)?
+                {
+                    self.rg.blue(self.buf)?;
+                }
                 self.buf
                     .print_dir(&visitor, &self.path_builder, self.rg.dir)?;
                 self.rg.reset(self.buf)?;
-                self.buf.newline()?;
+                self.buf
+                    .print_indicator(visitor.metadata(), self.rg.indicator)?;
+                let mount = self.mounts.get_child(visitor.filename()).cloned();
+                let mounted = self.print_mount(mount.as_ref())?;
 
-                // TODO: Should this be in register?
-                if self.level.can_descend_further() {
-                    self.level.add_one();
-                    // If folder needed permission, we skip it. Safe to use unwrap.
-                    if self
-                        .walk_dir(visitor.absolute_path().unwrap().clone())
-                        .is_err()
-                    {
-                        self.dir_stats.err_dirs_add_one();
-                        self.level.subtract_one();
-                        self.nod.pop();
-                        continue;
+                if self.one_fs.crosses(visitor.metadata()) {
+                    if !mounted {
+                        self.buf.write_message(" [mount point]")?;
                     }
-                    self.level.subtract_one();
+                    self.buf.newline()?;
+                } else {
+                    self.buf.newline()?;
+                    self.descend(visitor.absolute_path().unwrap().clone());
                 }
                 self.nod.pop();
                 continue;
@@ -132,15 +264,225 @@ impl<'tr, 'a> TreeCtxt<'tr, 'a> {
                 // - Special File(Device File, Socket File, Named Pipe (FIFO))
                 // - Unix-Specific(Block Device, Character Device)
                 self.dir_stats.special_add_one();
-                self.rg.bold_red(self.buf)?;
-                self.buf.write_os_string(visitor.filename().clone())?;
+                if !self.paint_git_entry(&visitor)?
+                    && !self.paint_theme(|theme| theme.entry("special"))?
+                    && !self.paint_ls_colors(&visitor)?
+                {
+                    self.rg.bold_red(self.buf)?;
+                }
+                self.buf
+                    .print_file(&visitor, &self.path_builder, self.rg.file)?;
                 self.rg.reset(self.buf)?;
+                self.buf
+                    .print_indicator(visitor.metadata(), self.rg.indicator)?;
                 self.buf.newline()?;
                 self.nod.pop();
                 continue;
             }
         }
 
+        for (idx, (name, count)) in missing.into_iter().enumerate() {
+            let status = walk::verify::Status::Missing;
+            self.dir_stats.verify_add(status, count);
+            self.nod.push_if(present_len + idx, entries_len);
+            self.nod.to_branch(&self.branch, self.buf)?;
+            self.paint_status(status)?;
+            self.buf.write_os_string(name)?;
+            self.rg.reset(self.buf)?;
+            self.print_status(Some(status))?;
+            self.buf.newline()?;
+            self.nod.pop();
+        }
+
+        Ok(())
+    }
+
+    /// Annotate a mount point with the filesystem mounted on it, e.g. `[tmpfs]`.
+    fn print_mount(&mut self, mount: Option<&walk::mount::Mount>) -> anyhow::Result<bool> {
+        match mount {
+            Some(mount) => {
+                let label = mount.label();
+                self.buf.write_message(" [")?;
+                self.buf.write_name(std::ffi::OsStr::new(&label))?;
+                self.buf.write_message("]")?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Walk into a directory one level further down, if the level bounds allow it.
+    fn descend(&mut self, path: std::path::PathBuf) {
+        // TODO: Should this be in register?
+        if self.level.can_descend_further() {
+            self.level.add_one();
+            // If folder needed permission, we skip it.
+            if self.walk_dir(path).is_err() {
+                self.dir_stats.err_dirs_add_one();
+            }
+            self.level.subtract_one();
+        }
+    }
+
+    /// Compare a regular file against the manifest given to `--verify`.
+    fn verify_status(&mut self, visitor: &walk::visit::Visitor) -> Option<walk::verify::Status> {
+        let path = visitor.absolute_path()?;
+        let base_path = self.path_builder.base_path();
+        let relative_path = path.strip_prefix(&base_path).ok()?;
+        // Reuse the digest column's hash when it used the same algorithm
+        let known = self
+            .digest
+            .last()
+            .filter(|_| self.digest.algo() == self.verifier.algo());
+        let status = self
+            .verifier
+            .check(path, visitor.metadata(), relative_path, known)?;
+        self.dir_stats.verify_add(status, 1);
+        Some(status)
+    }
+
+    /// Colour with the theme's style picked by `style`, if the theme defines one.
+    fn paint_theme(
+        &mut self,
+        style: impl Fn(&config::theme::Theme) -> Option<&str>,
+    ) -> anyhow::Result<bool> {
+        match style(&self.theme) {
+            Some(style) => {
+                self.rg.paint(self.buf, style)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Colour an entry with its `LS_COLORS` style, if one is defined.
+    fn paint_ls_colors(&mut self, visitor: &walk::visit::Visitor) -> anyhow::Result<bool> {
+        let Some(path) = visitor.absolute_path() else {
+            return Ok(false);
+        };
+
+        match self.ls_colors.style(path, visitor.metadata()) {
+            Some(style) => {
+                self.rg.paint(self.buf, style)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Colour a file by the theme's or the config's colour for its category.
+    fn paint_theme_category(&mut self, visitor: &walk::visit::Visitor) -> anyhow::Result<bool> {
+        let Some(category) = visitor.category().and_then(|idx| self.categories.get(idx)) else {
+            return Ok(false);
+        };
+
+        match self
+            .theme
+            .category(category.name())
+            .or_else(|| category.color())
+        {
+            Some(style) => {
+                self.rg.paint(self.buf, style)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Colour a file by the built-in palette for its category.
+    fn paint_builtin_category(&mut self, visitor: &walk::visit::Visitor) -> anyhow::Result<bool> {
+        let Some(category) = visitor.category().and_then(|idx| self.categories.get(idx)) else {
+            return Ok(false);
+        };
+
+        // Media is coloured by extension, the other kinds only once sniffed by `--detect-type`.
+        match (category.name(), visitor.kind().is_some()) {
+            ("image" | "video" | "audio", _) => self.rg.purple(self.buf)?,
+            ("archive", true) => self.rg.red(self.buf)?,
+            ("executable", true) => self.rg.bold_green(self.buf)?,
+            ("document", true) => self.rg.cyan(self.buf)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn paint_status(&mut self, status: walk::verify::Status) -> anyhow::Result<()> {
+        use walk::verify::Status;
+
+        if self.paint_theme(|theme| theme.status(status.label()))? {
+            return Ok(());
+        }
+
+        match status {
+            Status::Ok => self.rg.green(self.buf)?,
+            Status::Modified => self.rg.yellow(self.buf)?,
+            Status::Missing => self.rg.bold_red(self.buf)?,
+            Status::Extra => self.rg.purple(self.buf)?,
+        }
+        Ok(())
+    }
+
+    fn paint_git(&mut self, status: Option<walk::git::GitStatus>) -> anyhow::Result<bool> {
+        use walk::git::GitStatus;
+
+        let Some(status) = status else {
+            return Ok(false);
+        };
+
+        if status != GitStatus::Clean && self.paint_theme(|theme| theme.status(status.label()))? {
+            return Ok(true);
+        }
+
+        match status {
+            GitStatus::Modified => self.rg.yellow(self.buf)?,
+            GitStatus::Staged => self.rg.green(self.buf)?,
+            GitStatus::Untracked => self.rg.purple(self.buf)?,
+            GitStatus::Ignored => self.rg.cyan(self.buf)?,
+            GitStatus::Conflicted => self.rg.bold_red(self.buf)?,
+            GitStatus::Clean => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Colour an entry's name by its git status, if it is not clean.
+    fn paint_git_entry(&mut self, visitor: &walk::visit::Visitor) -> anyhow::Result<bool> {
+        let Some(path) = visitor.absolute_path().filter(|_| self.git.is_loaded()) else {
+            return Ok(false);
+        };
+        let status = self.git.status(path, &self.path_builder.base_path());
+        self.paint_git(status)
+    }
+
+    /// Append the verification verdict, e.g. ` [modified]`.
+    fn print_status(&mut self, status: Option<walk::verify::Status>) -> anyhow::Result<()> {
+        if let Some(status) = status {
+            self.buf.write_message(" [")?;
+            self.paint_status(status)?;
+            self.buf.write_message(status.label())?;
+            self.rg.reset(self.buf)?;
+            self.buf.write_message("]")?;
+        }
+        Ok(())
+    }
+
+    /// Point hard links back to the first path their inode was printed at.
+    fn print_hardlink(&mut self, visitor: &walk::visit::Visitor) -> anyhow::Result<()> {
+        if !self.hardlinks.is_enabled() {
+            return Ok(());
+        }
+
+        let path_builder = &self.path_builder;
+        let path = || path_builder.clone().append_relative(visitor).to_os_string();
+
+        if let Some(first) = self.hardlinks.first_seen(visitor.metadata(), path) {
+            self.buf.write_message(" => ")?;
+            if !self.paint_theme(|theme| theme.entry("target"))? {
+                self.rg.underlined_blue(self.buf)?;
+            }
+            self.buf.write_os_string(first)?;
+            self.rg.reset(self.buf)?;
+        }
+
         Ok(())
     }
 
@@ -153,27 +495,118 @@ impl<'tr, 'a> TreeCtxt<'tr, 'a> {
         let fmeta = self.path_builder.metadata()?;
 
         self.dir_stats.add_size(fmeta.size());
+        self.dir_stats
+            .add_disk_usage(walk::visit::allocated_size(&fmeta));
 
-        self.print_info(&fmeta).unwrap();
+        self.print_info(&fmeta, &base_path, None).unwrap();
 
-        self.rg.blue(self.buf)?;
+        self.buf.print_icon(&file_name, &fmeta, self.rg.icon)?;
+        match self
+            .theme
+            .entry("directory")
+            .or_else(|| self.ls_colors.style(&base_path, &fmeta))
+        {
+            Some(style) => self.rg.paint(self.buf, style)?,
+            None => self.rg.blue(self.buf)?,
+        }
         self.buf
             .print_header(&fmeta, &base_path.clone(), &file_name, self.rg.head)?;
         self.rg.reset(self.buf)?;
+        let mount = self.mounts.get(&base_path).cloned();
+        self.print_mount(mount.as_ref())?;
         self.buf.newline()?;
 
         Ok(())
     }
 
-    pub fn print_info(&mut self, meta: &std::fs::Metadata) -> anyhow::Result<()> {
-        self.buf.print_permission(meta, self.rg.pms)?;
-        self.buf.print_btime(meta, self.rg.btime)?;
-        self.buf.print_mtime(meta, self.rg.mtime)?;
-        self.buf.print_atime(meta, self.rg.atime)?;
+    pub fn print_info(
+        &mut self,
+        meta: &std::fs::Metadata,
+        path: &std::path::Path,
+        kind: Option<walk::magic::FileKind>,
+    ) -> anyhow::Result<()> {
+        self.print_column("inode", |tr| tr.buf.print_inode(meta, tr.rg.inode))?;
+        self.print_column("device", |tr| tr.buf.print_device(meta, tr.rg.device))?;
+        let acl = self.acl.check(path);
+        self.print_column("permission", |tr| {
+            tr.buf.print_permission(meta, acl, tr.rg.pms)
+        })?;
+
+        let git = self.git.status(path, &self.path_builder.base_path());
+        let painted = self.paint_git(git)?;
+        self.buf.print_git(git, self.rg.git)?;
+        if painted {
+            self.rg.reset(self.buf)?;
+        }
+
+        self.print_column("links", |tr| tr.buf.print_links(meta, tr.rg.links))?;
+        self.print_column("user", |tr| {
+            tr.buf.print_user(meta, &mut tr.accounts, tr.rg.user)
+        })?;
+        self.print_column("group", |tr| {
+            tr.buf.print_group(meta, &mut tr.accounts, tr.rg.group)
+        })?;
+        self.print_column("btime", |tr| {
+            tr.buf.print_btime(meta, &tr.timefmt, tr.rg.btime)
+        })?;
+        self.print_column("mtime", |tr| {
+            tr.buf.print_mtime(meta, &tr.timefmt, tr.rg.mtime)
+        })?;
+        self.print_column("atime", |tr| {
+            tr.buf.print_atime(meta, &tr.timefmt, tr.rg.atime)
+        })?;
+        self.print_column("ctime", |tr| {
+            tr.buf.print_ctime(meta, &tr.timefmt, tr.rg.ctime)
+        })?;
 
-        self.rg.green(self.buf)?;
+        if !self.paint_theme(|theme| theme.column("size"))? {
+            self.rg.green(self.buf)?;
+        }
         self.buf.print_size(meta, self.rg.size)?;
         self.rg.reset(self.buf)?;
+
+        if self.digest.is_enabled() {
+            let base_path = self.path_builder.base_path();
+            let relative_path = path.strip_prefix(&base_path).unwrap_or(path);
+            let sum = self
+                .digest
+                .compute(meta, path, relative_path)?
+                .unwrap_or_else(|| self.digest.placeholder());
+            self.print_column("hash", |tr| tr.buf.print_hash(&sum, tr.rg.hash))?;
+        }
+        self.print_column("type", |tr| tr.buf.print_file_type(kind, tr.rg.ftype))?;
+
+        // The root's children are printed at the first level too.
+        let depth = self.level.remaining() + i32::from(*path == self.path_builder.base_path());
+        let lines = self
+            .line_counter
+            .lines(path, meta, &self.rg, &self.follow, depth);
+        if let (true, Some(lines)) = (meta.is_file(), lines) {
+            self.code_stats.add(path, lines);
+        }
+        self.print_column("lines", |tr| tr.buf.print_lines(lines, tr.rg.lines))?;
+
+        let base_path = self.path_builder.base_path();
+        self.print_column("git_log", |tr| {
+            let commit = tr.git_history.last_commit(path, &base_path);
+            tr.buf.print_git_log(commit, &tr.timefmt, tr.rg.git_log)
+        })?;
+        self.print_column("xattr", |tr| tr.buf.print_xattr(path, tr.rg.xattr))?;
+        self.print_column("context", |tr| tr.buf.print_context(path, tr.rg.context))?;
+        Ok(())
+    }
+
+    /// Print a metadata column, painted with its theme colour if any.
+    fn print_column(
+        &mut self,
+        column: &str,
+        print: impl FnOnce(&mut Self) -> std::io::Result<()>,
+    ) -> anyhow::Result<()> {
+        let painted = self.paint_theme(|theme| theme.column(column))?;
+        print(self)?;
+        if painted {
+            self.rg.reset(self.buf)?;
+        }
         Ok(())
     }
 
@@ -191,6 +624,14 @@ impl<'tr, 'a> TreeCtxt<'tr, 'a> {
 
         self.buf.write_message(&summary)?;
         self.buf.newline()?;
+
+        if self.code_stats.is_enabled() {
+            self.buf.newline()?;
+            for row in self.code_stats.to_table() {
+                self.buf.write_message(&row)?;
+                self.buf.newline()?;
+            }
+        }
         Ok(())
     }
 }
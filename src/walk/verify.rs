@@ -0,0 +1,382 @@
+use super::digest::file_id;
+use super::digest::HashAlgo;
+use super::digest::ALGORITHM_COMMENT;
+use super::digest::SIZE_COMMENT;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::fs::Metadata;
+use std::path::Path;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    /// Digest matches the manifest.
+    Ok,
+    /// Digest differs from the manifest.
+    Modified,
+    /// Listed in the manifest but absent from the tree.
+    Missing,
+    /// Present in the tree but not listed in the manifest.
+    Extra,
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Modified => "modified",
+            Status::Missing => "missing",
+            Status::Extra => "extra",
+        }
+    }
+}
+
+/// What the manifest recorded for one file.
+#[derive(Debug, Clone, PartialEq)]
+struct Expected {
+    sum: String,
+    /// Size from the preceding `# size` comment, absent in plain `sha256sum` output.
+    size: Option<u64>,
+}
+
+/// Parsed `--verify` manifest.
+#[derive(Debug, Default)]
+struct Manifest {
+    /// Algorithm named by the `# algorithm:` comment.
+    algo: Option<HashAlgo>,
+    /// Relative path to what the manifest expects of it.
+    digests: HashMap<PathBuf, Expected>,
+    /// Relative directory to the children the manifest lists below it, with
+    /// the number of files each child accounts for.
+    children: HashMap<PathBuf, BTreeMap<OsString, usize>>,
+}
+
+/// Compares the walked tree against a manifest produced by `--manifest`.
+#[derive(Debug, Default)]
+pub struct Verifier {
+    /// `None` when verification is off.
+    expected: Option<Manifest>,
+    algo: Option<HashAlgo>,
+    /// `(dev, inode)` of the manifest, which is never part of itself.
+    manifest_id: Option<(u64, u64)>,
+}
+
+impl Verifier {
+    /// Loads the manifest at `path`, hashing with the algorithm it names or
+    /// else with `fallback`.
+    pub fn with_manifest(&mut self, path: &Path, fallback: HashAlgo) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let content =
+            fs::read(path).with_context(|| format!("Cannot read manifest '{}'", path.display()))?;
+
+        let manifest = parse_manifest(&content)?;
+        self.algo = Some(manifest.algo.unwrap_or(fallback));
+        self.expected = Some(manifest);
+        self.manifest_id = fs::metadata(path).ok().as_ref().and_then(file_id);
+        Ok(())
+    }
+
+    pub fn algo(&self) -> Option<HashAlgo> {
+        self.algo
+    }
+
+    /// Checks the regular file at `path`, listed as `relative_path` in the
+    /// manifest. `known` is a digest already computed with the verify
+    /// algorithm, saving a second read of the file.
+    pub fn check(
+        &self,
+        path: &Path,
+        meta: &Metadata,
+        relative_path: &Path,
+        known: Option<&str>,
+    ) -> Option<Status> {
+        let expected = self.expected.as_ref()?;
+        let algo = self.algo?;
+
+        if self.manifest_id.is_some() && file_id(meta) == self.manifest_id {
+            return None;
+        }
+
+        let Some(entry) = expected.digests.get(relative_path) else {
+            return Some(Status::Extra);
+        };
+
+        if entry.size.is_some_and(|size| size != meta.len()) {
+            return Some(Status::Modified);
+        }
+
+        let actual = match known {
+            Some(sum) => Ok(sum.to_string()),
+            None => algo.digest_file(path),
+        };
+        match actual {
+            Ok(actual) if actual.eq_ignore_ascii_case(&entry.sum) => Some(Status::Ok),
+            _ => Some(Status::Modified),
+        }
+    }
+
+    /// Lists the children of the directory `dir` (listed as `relative_dir`)
+    /// that the manifest expects but which no longer exist, together with
+    /// the number of manifest files each one accounts for.
+    pub fn missing_in(&self, dir: &Path, relative_dir: &Path) -> Vec<(OsString, usize)> {
+        let Some(expected) = self.expected.as_ref() else {
+            return Vec::new();
+        };
+
+        let Some(children) = expected.children.get(relative_dir) else {
+            return Vec::new();
+        };
+
+        children
+            .iter()
+            .filter(|(child, _)| fs::symlink_metadata(dir.join(child)).is_err())
+            .map(|(child, count)| (child.clone(), *count))
+            .collect()
+    }
+}
+
+/// Parses `<digest>  <path>` lines as written by `sha256sum` and friends,
+/// along with the `#` comments `--manifest` adds. Paths are raw bytes, as
+/// names need not be valid UTF-8.
+fn parse_manifest(content: &[u8]) -> anyhow::Result<Manifest> {
+    let mut expected = Manifest::default();
+    let mut size = None;
+
+    for (index, line) in content.split(|&byte| byte == b'\n').enumerate() {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.trim_ascii().is_empty() {
+            continue;
+        }
+
+        if line.starts_with(b"#") {
+            let comment = String::from_utf8_lossy(line);
+            if let Some(name) = comment.strip_prefix(ALGORITHM_COMMENT) {
+                expected.algo = Some(HashAlgo::from_name(name.trim()).ok_or_else(|| {
+                    anyhow::anyhow!("Unknown algorithm '{name}' on manifest line {}", index + 1)
+                })?);
+            } else if let Some(len) = comment.strip_prefix(SIZE_COMMENT) {
+                size = len.trim().parse().ok();
+            }
+            continue;
+        }
+
+        let (escaped, line) = match line.strip_prefix(b"\\") {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+
+        let malformed = || {
+            anyhow::anyhow!(
+                "Malformed manifest line {}: '{}'",
+                index + 1,
+                String::from_utf8_lossy(line)
+            )
+        };
+        let split = line
+            .iter()
+            .position(|&byte| byte == b' ')
+            .ok_or_else(malformed)?;
+        let sum = std::str::from_utf8(&line[..split]).map_err(|_| malformed())?;
+        let path = &line[split + 1..];
+
+        // Text mode uses a second space, binary mode a `*` before the path.
+        let path = path
+            .strip_prefix(b" ")
+            .or_else(|| path.strip_prefix(b"*"))
+            .unwrap_or(path);
+
+        let path = if escaped {
+            unescape(path)
+        } else {
+            path.to_vec()
+        };
+
+        let path = PathBuf::from(os_string(path));
+        for dir in path.ancestors().skip(1) {
+            let Some(child) = path
+                .strip_prefix(dir)
+                .ok()
+                .and_then(|rest| rest.iter().next())
+            else {
+                continue;
+            };
+            *expected
+                .children
+                .entry(dir.to_path_buf())
+                .or_default()
+                .entry(child.to_os_string())
+                .or_default() += 1;
+        }
+        expected.digests.insert(
+            path,
+            Expected {
+                sum: sum.to_string(),
+                size: size.take(),
+            },
+        );
+    }
+
+    Ok(expected)
+}
+
+/// Reverts the `\\`, `\n` and `\r` escapes of manifest paths.
+fn unescape(path: &[u8]) -> Vec<u8> {
+    let mut unescaped = Vec::with_capacity(path.len());
+    let mut bytes = path.iter();
+
+    while let Some(&byte) = bytes.next() {
+        if byte != b'\\' {
+            unescaped.push(byte);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'n') => unescaped.push(b'\n'),
+            Some(b'r') => unescaped.push(b'\r'),
+            Some(&other) => unescaped.push(other),
+            None => unescaped.push(b'\\'),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(unix)]
+fn os_string(bytes: Vec<u8>) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
+    OsString::from_vec(bytes)
+}
+
+#[cfg(not(unix))]
+fn os_string(bytes: Vec<u8>) -> OsString {
+    OsString::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::ffi::OsStr;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_manifest() {
+        let content = b"abc  a.txt\ndef *dir/b.bin\n\n";
+        let expected = parse_manifest(content).unwrap().digests;
+
+        assert_eq!(expected.get(Path::new("a.txt")).unwrap().sum, "abc");
+        assert_eq!(expected.get(Path::new("dir/b.bin")).unwrap().sum, "def");
+        assert!(parse_manifest(b"no-separator").is_err());
+
+        let expected = parse_manifest(b"\\abc  new\\nline\\\\x\n").unwrap().digests;
+        assert_eq!(expected.get(Path::new("new\nline\\x")).unwrap().sum, "abc");
+
+        let manifest = parse_manifest(b"# algorithm: xxh3\n# size 4\nabc  a\nabc  b\n").unwrap();
+        assert_eq!(manifest.algo, Some(HashAlgo::Xxh3));
+        assert_eq!(manifest.digests.get(Path::new("a")).unwrap().size, Some(4));
+        assert_eq!(manifest.digests.get(Path::new("b")).unwrap().size, None);
+        assert!(parse_manifest(b"# algorithm: md5\n").is_err());
+    }
+
+    #[test]
+    fn test_check_and_missing() {
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let root = temp_dir.path();
+
+        let mut file = File::create(root.join("same.txt")).unwrap();
+        write!(file, "same").unwrap();
+        let mut file = File::create(root.join("changed.txt")).unwrap();
+        write!(file, "changed").unwrap();
+        File::create(root.join("new.txt")).unwrap();
+
+        let algo = HashAlgo::Sha256;
+        let same = algo.digest(b"same".as_slice()).unwrap();
+        let manifest = root.join("manifest");
+        fs::write(
+            &manifest,
+            format!("{same}  same.txt\n{same}  changed.txt\n{same}  gone.txt\n{same}  lost/a\n{same}  lost/b\n"),
+        )
+        .unwrap();
+
+        let mut verifier = Verifier::default();
+        verifier.with_manifest(&manifest, algo).unwrap();
+
+        let check = |name: &str| {
+            let path = root.join(name);
+            verifier.check(&path, &fs::metadata(&path).unwrap(), Path::new(name), None)
+        };
+        assert_eq!(check("same.txt"), Some(Status::Ok));
+        assert_eq!(check("changed.txt"), Some(Status::Modified));
+        assert_eq!(check("new.txt"), Some(Status::Extra));
+        assert_eq!(check("manifest"), None);
+
+        let missing = verifier.missing_in(root, Path::new(""));
+        assert_eq!(
+            missing,
+            vec![(OsString::from("gone.txt"), 1), (OsString::from("lost"), 2)]
+        );
+
+        // A size mismatch is caught before hashing, a known digest is trusted
+        let path = root.join("same.txt");
+        let meta = fs::metadata(&path).unwrap();
+        fs::write(&manifest, format!("# size 99\n{same}  same.txt\n")).unwrap();
+        verifier.with_manifest(&manifest, algo).unwrap();
+        assert_eq!(
+            verifier.check(&path, &meta, Path::new("same.txt"), None),
+            Some(Status::Modified)
+        );
+        fs::write(&manifest, format!("{same}  same.txt\n")).unwrap();
+        verifier.with_manifest(&manifest, algo).unwrap();
+        assert_eq!(
+            verifier.check(&path, &meta, Path::new("same.txt"), Some("bad")),
+            Some(Status::Modified)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_manifest_round_trip() {
+        use super::super::digest::Digest;
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let root = temp_dir.path();
+        let names = [
+            OsStr::from_bytes(b"plain.txt"),
+            OsStr::from_bytes(b"caf\xe9.txt"),
+            OsStr::from_bytes(b"new\nline"),
+        ];
+        for name in names {
+            fs::write(root.join(name), name.as_bytes()).unwrap();
+        }
+
+        let manifest = root.join("manifest");
+        let mut digest = Digest::default();
+        digest.with_algo(HashAlgo::Blake3);
+        digest.with_manifest(&manifest).unwrap();
+        for name in names {
+            let path = root.join(name);
+            digest
+                .compute(&fs::metadata(&path).unwrap(), &path, Path::new(name))
+                .unwrap();
+        }
+        digest.finish().unwrap();
+
+        let mut verifier = Verifier::default();
+        verifier.with_manifest(&manifest, HashAlgo::Sha256).unwrap();
+        assert_eq!(verifier.algo(), Some(HashAlgo::Blake3));
+        for name in names {
+            let path = root.join(name);
+            let meta = fs::metadata(&path).unwrap();
+            assert_eq!(
+                verifier.check(&path, &meta, Path::new(name), None),
+                Some(Status::Ok)
+            );
+        }
+    }
+}
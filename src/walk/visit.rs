@@ -1,18 +1,13 @@
-use std::ffi::OsStr;
+use super::magic::FileKind;
+
 use std::ffi::OsString;
 use std::fs;
 use std::fs::DirEntry;
 use std::fs::FileType;
 use std::fs::Metadata;
+use std::io;
 use std::path::PathBuf;
 
-// TODO: Add more extension
-static MEDIA_EXTENSIONS: phf::Set<&'static str> = phf::phf_set! {
-    "jpg", "jpeg", "png", "gif", "bmp", "tiff", "webp",     // Images
-    "mp4", "avi", "mkv", "mov", "flv", "wmv", "webm",       // Videos
-    "mp3", "wav", "ogg", "flac", "aac", "m4a"             // Audio
-};
-
 #[derive(Debug)]
 pub struct Visitor {
     abs: Option<PathBuf>,
@@ -21,7 +16,11 @@ pub struct Visitor {
     filety: FileType,
     meta: Metadata,
     size: Option<u64>,
-    is_media: bool,
+    disk_usage: Option<u64>,
+    /// Index into `Categories`, only set for regular files.
+    category: Option<usize>,
+    /// Only known when the content has been sniffed with `--detect-type`.
+    kind: Option<FileKind>,
 }
 
 impl Visitor {
@@ -37,12 +36,7 @@ impl Visitor {
             .ok_or_else(|| anyhow!("Cannot get filename for path '{:?}'", path))?;
 
         let size = metadata.len();
-
-        let is_media = path
-            .extension()
-            .and_then(OsStr::to_str)
-            .map(|ext| MEDIA_EXTENSIONS.contains(&ext.to_lowercase()))
-            .unwrap_or(false);
+        let disk_usage = allocated_size(&metadata);
 
         Ok(Self {
             abs: Some(path),
@@ -51,7 +45,9 @@ impl Visitor {
             meta: metadata,
             filename,
             size: Some(size),
-            is_media,
+            disk_usage: Some(disk_usage),
+            category: None,
+            kind: None,
         })
     }
 
@@ -88,12 +84,38 @@ impl Visitor {
         self.size
     }
 
+    /// Space allocated on disk for the entry, which differs from `size`
+    /// for sparse and compressed files.
+    pub fn disk_usage(&self) -> Option<u64> {
+        self.disk_usage
+    }
+
     pub fn metadata(&self) -> &Metadata {
         &self.meta
     }
 
-    pub fn is_media_type(&self) -> bool {
-        self.is_media
+    pub fn with_kind(&mut self, kind: Option<FileKind>) {
+        self.kind = kind;
+    }
+
+    pub fn kind(&self) -> Option<FileKind> {
+        self.kind
+    }
+
+    pub fn with_category(&mut self, category: Option<usize>) {
+        self.category = category;
+    }
+
+    pub fn category(&self) -> Option<usize> {
+        self.category
+    }
+
+    /// Metadata of the entry a symlink points to.
+    pub fn target_metadata(&self) -> io::Result<Metadata> {
+        let path = self
+            .absolute_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid absolute path"))?;
+        fs::metadata(path)
     }
 
     pub fn get_target_symlink(&self) -> anyhow::Result<PathBuf> {
@@ -110,3 +132,49 @@ impl Visitor {
             .with_context(|| format!("Cannot read target link for symlink '{path:?}'"))
     }
 }
+
+/// Whether `err`, from reading a symlink's target, means the link is dangling
+/// rather than merely unreadable.
+#[cfg(unix)]
+pub fn is_dangling(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::NotFound || err.raw_os_error() == Some(libc::ELOOP)
+}
+
+#[cfg(not(unix))]
+pub fn is_dangling(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::NotFound
+}
+
+/// Returns the number of bytes allocated for the entry (`st_blocks * 512`).
+#[cfg(unix)]
+pub fn allocated_size(meta: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.blocks() * 512
+}
+
+#[cfg(not(unix))]
+pub fn allocated_size(meta: &Metadata) -> u64 {
+    meta.len()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::symlink;
+
+    #[test]
+    fn test_is_dangling() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let missing = temp_dir.path().join("missing");
+        let looping = temp_dir.path().join("loop");
+        symlink("nowhere", &missing).unwrap();
+        symlink("loop", &looping).unwrap();
+
+        assert!(is_dangling(&fs::metadata(&missing).unwrap_err()));
+        assert!(is_dangling(&fs::metadata(&looping).unwrap_err()));
+        assert!(!is_dangling(&io::Error::from(
+            io::ErrorKind::PermissionDenied
+        )));
+    }
+}
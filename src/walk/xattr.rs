@@ -0,0 +1,122 @@
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// Extended attributes holding a POSIX access list.
+const ACL_ATTRS: [&str; 2] = ["system.posix_acl_access", "system.posix_acl_default"];
+
+/// Extended attribute holding the SELinux security context.
+const SELINUX_ATTR: &str = "security.selinux";
+
+/// Checks whether entries carry a POSIX ACL, for the `+` after the mode.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Acl {
+    enabled: bool,
+}
+
+impl Acl {
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Returns `None` unless `--acl` was requested.
+    pub fn check(&self, path: &Path) -> Option<bool> {
+        self.enabled.then(|| has_acl(path))
+    }
+}
+
+/// Names of the extended attributes of `path`, without following symlinks.
+pub fn names(path: &Path) -> Vec<String> {
+    let Ok(attrs) = xattr::list(path) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = attrs
+        .map(|name| name.to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+pub fn has_acl(path: &Path) -> bool {
+    xattr::list(path)
+        .map(|mut attrs| attrs.any(|name| ACL_ATTRS.iter().any(|acl| name == OsStr::new(acl))))
+        .unwrap_or(false)
+}
+
+/// SELinux label of `path`, e.g. `system_u:object_r:etc_t:s0`.
+pub fn security_context(path: &Path) -> Option<String> {
+    let label = xattr::get(path, SELINUX_ATTR).ok()??;
+    Some(parse_label(&label))
+}
+
+/// The kernel stores the label NUL-terminated.
+fn parse_label(label: &[u8]) -> String {
+    let label = label.strip_suffix(b"\0").unwrap_or(label);
+    String::from_utf8_lossy(label).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_names_and_acl() {
+        let file = NamedTempFile::new().expect("Failed to create temporary file");
+        let path = file.path();
+        assert!(!has_acl(path));
+
+        // Skip where the filesystem does not support user xattrs.
+        if xattr::set(path, "user.b", b"1").is_err() {
+            return;
+        }
+        xattr::set(path, "user.a", b"2").unwrap();
+        assert_eq!(names(path), ["user.a", "user.b"]);
+        assert!(!has_acl(path));
+
+        let mut acl = Acl::default();
+        assert_eq!(acl.check(path), None);
+        acl.enable();
+        assert_eq!(acl.check(path), Some(false));
+
+        // Version 2 header, then user::rw-, user:1000:r--, group::r--,
+        // mask::r-- and other::r--. An ACL the mode bits could express is
+        // folded into the mode, so this one names a user.
+        let entries: [(u16, u16, u32); 5] = [
+            (0x01, 6, u32::MAX),
+            (0x02, 4, 1000),
+            (0x04, 4, u32::MAX),
+            (0x10, 4, u32::MAX),
+            (0x20, 4, u32::MAX),
+        ];
+        let mut value = 2u32.to_le_bytes().to_vec();
+        for (tag, perm, id) in entries {
+            value.extend(tag.to_le_bytes());
+            value.extend(perm.to_le_bytes());
+            value.extend(id.to_le_bytes());
+        }
+        if xattr::set(path, ACL_ATTRS[0], &value).is_ok() {
+            assert!(has_acl(path));
+        }
+    }
+
+    #[test]
+    fn test_security_context() {
+        assert_eq!(
+            parse_label(b"system_u:object_r:etc_t:s0\0"),
+            "system_u:object_r:etc_t:s0"
+        );
+        assert_eq!(parse_label(b"unlabeled_t"), "unlabeled_t");
+
+        let file = NamedTempFile::new().expect("Failed to create temporary file");
+        let label = b"system_u:object_r:tmp_t:s0\0";
+        // Only hosts with SELinux accept a label.
+        if xattr::set(file.path(), SELINUX_ATTR, label).is_ok() {
+            assert_eq!(
+                security_context(file.path()).as_deref(),
+                Some("system_u:object_r:tmp_t:s0")
+            );
+        }
+    }
+}